@@ -0,0 +1,140 @@
+use std::fmt::{self, Debug, Display};
+
+/// Error returned when an answer no longer fits in the chosen number type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overflow {
+    pub op: &'static str,
+    pub lhs: String,
+    pub rhs: String,
+}
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} overflowed", self.lhs, self.op, self.rhs)
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Number type puzzle answers are computed in.
+///
+/// Every operation is checked: instead of wrapping or panicking it returns an
+/// `Overflow` describing the operands, so a too-large input fails loudly.
+pub trait Number: Clone + Ord + Debug + Display {
+    fn zero() -> Self;
+    fn from_u64(value: u64) -> Self;
+    fn checked_add(&self, rhs: &Self) -> Result<Self, Overflow>;
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, Overflow>;
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, Overflow>;
+}
+
+fn overflow<N: Display>(op: &'static str, lhs: &N, rhs: &N) -> Overflow {
+    return Overflow {
+        op,
+        lhs: lhs.to_string(),
+        rhs: rhs.to_string(),
+    };
+}
+
+// The fast path: native checked arithmetic, no allocation.
+impl Number for u64 {
+    fn zero() -> Self {
+        return 0;
+    }
+
+    fn from_u64(value: u64) -> Self {
+        return value;
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Result<Self, Overflow> {
+        return u64::checked_add(*self, *rhs).ok_or_else(|| overflow("+", self, rhs));
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, Overflow> {
+        return u64::checked_sub(*self, *rhs).ok_or_else(|| overflow("-", self, rhs));
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, Overflow> {
+        return u64::checked_mul(*self, *rhs).ok_or_else(|| overflow("*", self, rhs));
+    }
+}
+
+// Arbitrary precision: only subtraction below zero can fail.
+#[cfg(feature = "bigint")]
+impl Number for num_bigint::BigUint {
+    fn zero() -> Self {
+        return num_bigint::BigUint::default();
+    }
+
+    fn from_u64(value: u64) -> Self {
+        return num_bigint::BigUint::from(value);
+    }
+
+    fn checked_add(&self, rhs: &Self) -> Result<Self, Overflow> {
+        return Ok(self + rhs);
+    }
+
+    fn checked_sub(&self, rhs: &Self) -> Result<Self, Overflow> {
+        if rhs > self {
+            return Err(overflow("-", self, rhs));
+        }
+        return Ok(self - rhs);
+    }
+
+    fn checked_mul(&self, rhs: &Self) -> Result<Self, Overflow> {
+        return Ok(self * rhs);
+    }
+}
+
+/// The number type the binaries print answers in.
+#[cfg(not(feature = "bigint"))]
+pub type Answer = u64;
+
+/// The number type the binaries print answers in.
+#[cfg(feature = "bigint")]
+pub type Answer = num_bigint::BigUint;
+
+pub fn sum<N: Number, I: IntoIterator<Item = N>>(values: I) -> Result<N, Overflow> {
+    return values
+        .into_iter()
+        .try_fold(N::zero(), |acc, value| acc.checked_add(&value));
+}
+
+pub fn product<N: Number, I: IntoIterator<Item = N>>(values: I) -> Result<N, Overflow> {
+    return values
+        .into_iter()
+        .try_fold(N::from_u64(1), |acc, value| acc.checked_mul(&value));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn sum_reports_overflow() {
+        assert_eq!(sum([1u64, 2, 3]), Ok(6));
+        assert_eq!(
+            sum([u64::MAX, 1]),
+            Err(Overflow {
+                op: "+",
+                lhs: u64::MAX.to_string(),
+                rhs: "1".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn product_and_sub_are_checked() {
+        assert_eq!(product([2u64, 3, 4]), Ok(24));
+        assert!(product([u64::MAX, 2]).is_err());
+        assert!(Number::checked_sub(&1u64, &2).is_err());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn bigint_does_not_overflow() {
+        let big = sum([Answer::from_u64(u64::MAX), Answer::from_u64(1)]).unwrap();
+        assert_eq!(big.to_string(), "18446744073709551616");
+    }
+}
@@ -0,0 +1,483 @@
+use advent_of_code::answer::{self, Answer, Number, Overflow};
+use advent_of_code::config::{self, Validate};
+use advent_of_code::{cli::Args, trace};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{self, Display};
+use std::fs;
+
+mod rebalance;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Elf {
+    id: usize,
+    items: Vec<u64>,
+}
+
+impl Elf {
+    fn total<N: Number>(&self) -> Result<N, Overflow> {
+        return answer::sum(self.items.iter().map(|&item| N::from_u64(item)));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ParseError {
+    line: usize,
+    elf: usize,
+    item: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: elf {} carries a non-numeric item {:?}",
+            self.line, self.elf, self.item
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    /// How many of the heaviest elves part two adds up (`--top N`)
+    top: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Self { top: 3 };
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.top == 0 {
+            return Err("top must be at least 1".to_string());
+        }
+        return Ok(());
+    }
+}
+
+fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day1");
+    let config: Config = config::load(&args, "day1").expect("Invalid config");
+    if args.flag("--stdin") {
+        follow_stdin(config.top);
+        return;
+    }
+    let file_path = "src/inputs/day1.txt";
+
+    let calories_input = fs::read_to_string(file_path).expect("Couldn't read file");
+
+    let elves = parse_elves(&calories_input).expect("Input is corrupt");
+    if let Some(threshold) = args.value("--rebalance") {
+        let threshold: u64 = threshold.parse().expect("--rebalance is not a number");
+        let budget = args.value("--budget").map_or(100_000, |budget| {
+            budget.parse().expect("--budget is not a number")
+        });
+        print_rebalance(&elves, threshold, budget);
+        return;
+    }
+    if args.flag("--report") {
+        print!("{}", Report::new(&elves).expect("Calory load overflowed"));
+        return;
+    }
+
+    let all_calory_loads: Vec<Answer> = calory_loads(&elves).expect("Calory load overflowed");
+
+    let largest_calory_load: Answer = largest_calory_load(&all_calory_loads);
+    let top_elves = top_k(&all_calory_loads, config.top);
+    if top_elves.len() < config.top {
+        println!(
+            "Only {} elves to pick the top {} from",
+            top_elves.len(),
+            config.top
+        );
+    }
+    for (elf, load) in &top_elves {
+        println!("Elf {} carries {}", elves[*elf].id, load);
+    }
+    let top_loads: Answer =
+        answer::sum(top_elves.into_iter().map(|(_, load)| load)).expect("Top loads overflowed");
+
+    dbg!(top_loads);
+    dbg!(largest_calory_load);
+}
+
+fn print_rebalance(elves: &[Elf], threshold: u64, budget: usize) {
+    let plan = match rebalance::plan(elves, threshold, budget) {
+        Ok(plan) => plan,
+        Err(err) => {
+            println!("Can't rebalance to {}: {}", threshold, err);
+            return;
+        }
+    };
+
+    for mv in &plan.moves {
+        println!("Elf {} gives {} to elf {}", mv.from, mv.item, mv.to);
+    }
+    rebalance::validate(elves, &plan.moves, threshold).expect("Rebalancing plan is invalid");
+    println!(
+        "{} items moved, nobody carries more than {}",
+        plan.moves.len(),
+        threshold
+    );
+    if !plan.optimal {
+        println!("(search budget ran out, fewer moves may be possible)");
+    }
+}
+
+/// Reads calorie entries from stdin as they arrive (e.g. `tail -f log | day1
+/// --stdin`) and prints the running answers every time an elf is finished.
+fn follow_stdin(top: usize) {
+    let mut tracker = ElfTracker::<Answer>::default();
+
+    for (idx, line) in std::io::stdin().lines().enumerate() {
+        let line = line.expect("Couldn't read stdin");
+        let calory_value = line.trim();
+        if calory_value.is_empty() {
+            if tracker.end_elf() {
+                println!(
+                    "elves: {}, max: {}, top {}: {}",
+                    tracker.elves(),
+                    tracker.max().unwrap(),
+                    top,
+                    tracker.top(top).expect("Top loads overflowed")
+                );
+            }
+            continue;
+        }
+
+        let item = calory_value
+            .parse::<u64>()
+            .unwrap_or_else(|_| panic!("line {}: non-numeric item {:?}", idx + 1, calory_value));
+        tracker.push_item(item).expect("Calory load overflowed");
+    }
+
+    if tracker.elves() > 0 {
+        println!(
+            "elves: {}, max: {}, top {}: {}",
+            tracker.elves(),
+            tracker.max().unwrap(),
+            top,
+            tracker.top(top).expect("Top loads overflowed")
+        );
+    }
+}
+
+/// Keeps day 1's answers up to date while calorie entries are fed one at a time.
+///
+/// Finished loads are kept sorted, so `max` is O(1) and `top(k)` is O(k). The
+/// elf still being fed counts as if its list ended now, which matches how
+/// `parse_elves` treats a file without a trailing blank line.
+#[derive(Debug)]
+struct ElfTracker<N> {
+    loads: Vec<N>,
+    current: Option<N>,
+}
+
+impl<N> Default for ElfTracker<N> {
+    fn default() -> Self {
+        return Self {
+            loads: vec![],
+            current: None,
+        };
+    }
+}
+
+impl<N: Number> ElfTracker<N> {
+    fn push_item(&mut self, calories: u64) -> Result<(), Overflow> {
+        let current = self.current.take().unwrap_or_else(N::zero);
+        self.current = Some(current.checked_add(&N::from_u64(calories))?);
+        return Ok(());
+    }
+
+    /// Closes the current elf, returning false if it had no items.
+    fn end_elf(&mut self) -> bool {
+        if let Some(load) = self.current.take() {
+            let idx = self.loads.partition_point(|other| *other < load);
+            self.loads.insert(idx, load);
+            return true;
+        }
+        return false;
+    }
+
+    fn elves(&self) -> usize {
+        return self.loads.len() + self.current.iter().count();
+    }
+
+    fn max(&self) -> Option<N> {
+        return self.loads.last().max(self.current.as_ref()).cloned();
+    }
+
+    fn top(&self, k: usize) -> Result<N, Overflow> {
+        let mut top: Vec<&N> = self.loads.iter().rev().take(k).collect();
+        if let Some(current) = &self.current {
+            let idx = top.partition_point(|other| *other >= current);
+            top.insert(idx, current);
+            top.truncate(k);
+        }
+        return answer::sum(top.into_iter().cloned());
+    }
+}
+
+/// Groups the calorie list into elves.
+///
+/// Elves are separated by one or more blank lines, where whitespace-only lines
+/// count as blank, and `\r\n` line endings and surrounding spaces are ignored.
+#[tracing::instrument(skip_all)]
+fn parse_elves(calories_input: &str) -> Result<Vec<Elf>, ParseError> {
+    let mut elves = vec![];
+    let mut items = vec![];
+
+    for (idx, line) in calories_input.lines().enumerate() {
+        let calory_value = line.trim();
+        if calory_value.is_empty() {
+            if !items.is_empty() {
+                elves.push(Elf {
+                    id: elves.len() + 1,
+                    items: std::mem::take(&mut items),
+                });
+            }
+            continue;
+        }
+
+        let item = calory_value.parse::<u64>().map_err(|_| ParseError {
+            line: idx + 1,
+            elf: elves.len() + 1,
+            item: calory_value.to_string(),
+        })?;
+        items.push(item);
+    }
+
+    if !items.is_empty() {
+        elves.push(Elf {
+            id: elves.len() + 1,
+            items,
+        });
+    }
+
+    return Ok(elves);
+}
+
+#[tracing::instrument(skip_all)]
+fn calory_loads<N: Number>(elves: &[Elf]) -> Result<Vec<N>, Overflow> {
+    return elves.iter().map(|elf| elf.total()).collect();
+}
+
+#[tracing::instrument(skip_all)]
+fn largest_calory_load<N: Number>(loads: &[N]) -> N {
+    let (_, largest) = top_k(loads, 1).pop().expect("No elves to pick from");
+    return largest;
+}
+
+/// The `k` heaviest elves as `(elf index, load)`, heaviest first.
+///
+/// Keeps a min-heap of the best `k` seen so far, so it is O(n log k) rather than a
+/// full sort. Returns every elf if there are fewer than `k`; ties go to the elf
+/// that came first.
+#[tracing::instrument(skip_all)]
+fn top_k<N: Number>(loads: &[N], k: usize) -> Vec<(usize, N)> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+
+    for (elf, load) in loads.iter().enumerate() {
+        heap.push(Reverse((load.clone(), Reverse(elf))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+
+    return heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((load, Reverse(elf)))| (elf, load))
+        .collect();
+}
+
+struct ElfStats {
+    id: usize,
+    items: usize,
+    total: u64,
+    percentile: f64,
+}
+
+/// Per-elf and overall statistics of the loads, printed by `--report`.
+struct Report {
+    elves: Vec<ElfStats>,
+    mean: f64,
+    median: f64,
+    histogram: Vec<(u64, u64, usize)>,
+}
+
+impl Report {
+    const BUCKETS: u64 = 10;
+
+    #[tracing::instrument(skip_all)]
+    fn new(elves: &[Elf]) -> Result<Self, Overflow> {
+        let totals: Vec<u64> = calory_loads(elves)?;
+        let mut sorted = totals.clone();
+        sorted.sort();
+        let count = sorted.len();
+
+        let elf_stats = elves
+            .iter()
+            .zip(&totals)
+            .map(|(elf, &total)| {
+                // percentile rank: elves carrying less, plus half of the ties
+                let below = sorted.partition_point(|&load| load < total);
+                let equal = sorted.partition_point(|&load| load <= total) - below;
+                return ElfStats {
+                    id: elf.id,
+                    items: elf.items.len(),
+                    total,
+                    percentile: (below as f64 + equal as f64 / 2.0) * 100.0 / count as f64,
+                };
+            })
+            .collect();
+
+        let mean = answer::sum(totals.iter().copied())? as f64 / count as f64;
+        let median = match count {
+            0 => f64::NAN,
+            _ if count.is_multiple_of(2) => {
+                (sorted[count / 2 - 1] + sorted[count / 2]) as f64 / 2.0
+            }
+            _ => sorted[count / 2] as f64,
+        };
+
+        let mut histogram = vec![];
+        if let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) {
+            let width = (max - min) / Self::BUCKETS + 1;
+            for bucket in 0..Self::BUCKETS {
+                let start = min + bucket * width;
+                let end = start + width - 1;
+                let elves = sorted
+                    .iter()
+                    .filter(|&&load| load >= start && load <= end)
+                    .count();
+                histogram.push((start, end, elves));
+            }
+        }
+
+        return Ok(Self {
+            elves: elf_stats,
+            mean,
+            median,
+            histogram,
+        });
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>5} {:>6} {:>8} {:>10}",
+            "elf", "items", "total", "percentile"
+        )?;
+        for elf in &self.elves {
+            writeln!(
+                f,
+                "{:>5} {:>6} {:>8} {:>10.1}",
+                elf.id, elf.items, elf.total, elf.percentile
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "elves: {}", self.elves.len())?;
+        writeln!(f, "mean: {:.1}", self.mean)?;
+        writeln!(f, "median: {:.1}", self.median)?;
+
+        writeln!(f)?;
+        let most = self.histogram.iter().map(|&(_, _, elves)| elves).max();
+        for &(start, end, elves) in &self.histogram {
+            let bar = "#".repeat(elves * 40 / most.unwrap_or(1).max(1));
+            writeln!(f, "{:>6}-{:<6} {:>4} {}", start, end, elves, bar)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../test_inputs/day1.test.txt");
+
+    #[test]
+    fn top_three_elves() {
+        let loads: Vec<u64> = calory_loads(&parse_elves(TEST_INPUT).unwrap()).unwrap();
+        assert_eq!(top_k(&loads, 3), vec![(3, 24000), (2, 11000), (4, 10000)]);
+        assert_eq!(largest_calory_load(&loads), 24000);
+    }
+
+    #[test]
+    fn rejects_bad_items() {
+        assert_eq!(
+            parse_elves("1000\n\n20x0"),
+            Err(ParseError {
+                line: 3,
+                elf: 2,
+                item: "20x0".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn tolerates_crlf_and_blank_runs() {
+        let messy = "1000\r\n2000  \r\n\r\n \t\r\n\r\n  4000\r\n\n\n\n5000\n\n";
+        let loads: Vec<u64> = calory_loads(&parse_elves(messy).unwrap()).unwrap();
+        assert_eq!(loads, vec![3000, 4000, 5000]);
+    }
+
+    #[test]
+    fn tracker_matches_batch_answers() {
+        let mut tracker = ElfTracker::<u64>::default();
+        for line in TEST_INPUT.lines() {
+            match line.parse::<u64>() {
+                Ok(item) => tracker.push_item(item).unwrap(),
+                Err(_) => assert!(tracker.end_elf()),
+            }
+        }
+
+        assert_eq!(tracker.elves(), 5);
+        assert_eq!(tracker.max(), Some(24000));
+        assert_eq!(tracker.top(3), Ok(45000));
+        assert_eq!(tracker.top(10), Ok(55000));
+
+        assert!(tracker.end_elf());
+        tracker.push_item(30000).unwrap();
+        assert_eq!(tracker.elves(), 6);
+        assert_eq!(tracker.max(), Some(30000));
+        assert_eq!(tracker.top(2), Ok(54000));
+    }
+
+    #[test]
+    fn report_statistics() {
+        let report = Report::new(&parse_elves(TEST_INPUT).unwrap()).unwrap();
+        assert_eq!(report.mean, 11000.0);
+        assert_eq!(report.median, 10000.0);
+        assert_eq!(report.elves[3].items, 3);
+        assert_eq!(report.elves[3].percentile, 90.0);
+        assert_eq!(
+            report
+                .histogram
+                .iter()
+                .map(|&(_, _, elves)| elves)
+                .sum::<usize>(),
+            5
+        );
+    }
+
+    #[test]
+    fn top_k_with_fewer_elves() {
+        assert_eq!(top_k(&[5u64, 7], 3), vec![(1, 7), (0, 5)]);
+        assert_eq!(top_k(&[5u64, 5], 1), vec![(0, 5)]);
+        assert_eq!(top_k::<u64>(&[], 3), vec![]);
+    }
+}
@@ -0,0 +1,446 @@
+use crate::Elf;
+use advent_of_code::answer::{self, Overflow};
+use std::fmt::{self, Display};
+
+/// One snack handed from one elf to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub item: u64,
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RebalanceError {
+    Overflow(Overflow),
+    /// A single item is heavier than the threshold, so no elf can carry it
+    ItemTooHeavy {
+        elf: usize,
+        item: u64,
+    },
+    /// Every way of moving the items was ruled out
+    NoRoom,
+    /// The search gave up before finding any plan
+    OutOfBudget,
+    MissingItem(Move),
+    OverThreshold {
+        elf: usize,
+        total: u64,
+    },
+}
+
+impl Display for RebalanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow(err) => write!(f, "{}", err),
+            Self::ItemTooHeavy { elf, item } => {
+                write!(
+                    f,
+                    "elf {} carries an item of {} over the threshold",
+                    elf, item
+                )
+            }
+            Self::NoRoom => write!(f, "the items don't fit however they are moved"),
+            Self::OutOfBudget => write!(f, "no plan found within the search budget"),
+            Self::MissingItem(mv) => {
+                write!(
+                    f,
+                    "elf {} has no item {} to give to elf {}",
+                    mv.from, mv.item, mv.to
+                )
+            }
+            Self::OverThreshold { elf, total } => {
+                write!(f, "elf {} still carries {}", elf, total)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RebalanceError {}
+
+impl From<Overflow> for RebalanceError {
+    fn from(err: Overflow) -> Self {
+        return Self::Overflow(err);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+    /// Whether the search finished, so no plan moves fewer items
+    pub optimal: bool,
+}
+
+/// Plans how to move items so that no elf carries more than `threshold`,
+/// moving as few items as possible.
+///
+/// Depth-first search deciding, heaviest item first, which elf ends up with
+/// each item: its owner if it still fits, then the other elves with the least
+/// room that fits it. Elves with nothing of their own left to place and the same
+/// load are interchangeable, so only one of them is tried. A branch is cut once
+/// its moves plus, for every elf, the fewest of its own items it must still give
+/// away can't beat the best plan so far. The search starts from a greedy plan
+/// (overloaded elves shed their heaviest items, placed best fit) and stops after
+/// visiting `budget` branches, keeping the best plan so far.
+pub fn plan(elves: &[Elf], threshold: u64, budget: usize) -> Result<Plan, RebalanceError> {
+    let mut items = vec![];
+    let mut rest = vec![];
+    for (pos, elf) in elves.iter().enumerate() {
+        for &item in &elf.items {
+            if item > threshold {
+                return Err(RebalanceError::ItemTooHeavy { elf: elf.id, item });
+            }
+            items.push((item, pos));
+        }
+        rest.push(elf.total::<u64>()?);
+    }
+    items.sort_by_key(|&(item, _)| std::cmp::Reverse(item));
+
+    let mut own: Vec<Vec<u64>> = vec![vec![]; elves.len()];
+    for &(item, pos) in &items {
+        own[pos].push(item);
+    }
+
+    let mut search = Search {
+        threshold,
+        best: greedy(&items, &rest, threshold).map(|targets| {
+            let moved = count_moved(&items, &targets);
+            return (moved, targets);
+        }),
+        targets: vec![0; items.len()],
+        load: vec![0; elves.len()],
+        placed: vec![0; elves.len()],
+        forced: vec![0; elves.len()],
+        bound: 0,
+        moved: 0,
+        items,
+        own,
+        rest,
+        budget,
+    };
+    for pos in 0..elves.len() {
+        search.update_forced(pos);
+    }
+    search.run(0);
+
+    let exhausted = search.budget == 0;
+    let Some((_, targets)) = search.best else {
+        if exhausted {
+            return Err(RebalanceError::OutOfBudget);
+        }
+        return Err(RebalanceError::NoRoom);
+    };
+    let moves = search
+        .items
+        .iter()
+        .zip(&targets)
+        .filter(|&(&(_, from), &to)| from != to)
+        .map(|(&(item, from), &to)| Move {
+            item,
+            from: elves[from].id,
+            to: elves[to].id,
+        })
+        .collect();
+
+    return Ok(Plan {
+        moves,
+        optimal: !exhausted,
+    });
+}
+
+struct Search {
+    threshold: u64,
+    /// Every item with the position of its owner, heaviest first
+    items: Vec<(u64, usize)>,
+    /// Each elf's own items, heaviest first
+    own: Vec<Vec<u64>>,
+    /// How many of each elf's own items are placed
+    placed: Vec<usize>,
+    /// Weight of each elf's own items not placed yet
+    rest: Vec<u64>,
+    /// Weight of the items placed on each elf
+    load: Vec<u64>,
+    /// Fewest own items each elf must still give away
+    forced: Vec<usize>,
+    /// Sum of `forced`
+    bound: usize,
+    /// Elf each placed item ends up with
+    targets: Vec<usize>,
+    moved: usize,
+    /// Fewest items moved so far, and where each item goes
+    best: Option<(usize, Vec<usize>)>,
+    budget: usize,
+}
+
+impl Search {
+    fn run(&mut self, idx: usize) {
+        let best = self.best.as_ref().map_or(usize::MAX, |(moved, _)| *moved);
+        if idx == self.items.len() {
+            if self.moved < best {
+                self.best = Some((self.moved, self.targets.clone()));
+            }
+            return;
+        }
+        if self.moved + self.bound >= best || self.budget == 0 {
+            return;
+        }
+        self.budget -= 1;
+
+        let (item, owner) = self.items[idx];
+        let fits = |elf: usize| item <= self.threshold - self.load[elf];
+        let mut options: Vec<usize> = (0..self.load.len())
+            .filter(|&elf| elf != owner && fits(elf))
+            .collect();
+        // least room first, then drop elves a previous option can stand in for
+        options.sort_by_key(|&elf| (std::cmp::Reverse(self.load[elf]), self.rest[elf] > 0));
+        options.dedup_by(|elf, prev| {
+            return self.rest[*elf] == 0
+                && self.rest[*prev] == 0
+                && self.load[*elf] == self.load[*prev];
+        });
+        if fits(owner) {
+            options.insert(0, owner);
+        }
+
+        for elf in options {
+            if self.budget == 0 {
+                break;
+            }
+            self.place(idx, elf, true);
+            self.run(idx + 1);
+            self.place(idx, elf, false);
+        }
+    }
+
+    /// Gives (or takes back) item `idx` to `elf`.
+    fn place(&mut self, idx: usize, elf: usize, placing: bool) {
+        let (item, owner) = self.items[idx];
+        if placing {
+            self.placed[owner] += 1;
+            self.rest[owner] -= item;
+            self.load[elf] += item;
+        } else {
+            self.placed[owner] -= 1;
+            self.rest[owner] += item;
+            self.load[elf] -= item;
+        }
+        if elf != owner {
+            if placing {
+                self.moved += 1;
+            } else {
+                self.moved -= 1;
+            }
+        }
+        self.targets[idx] = elf;
+        self.update_forced(owner);
+        self.update_forced(elf);
+    }
+
+    /// Recounts how many of its heaviest unplaced items `elf` must give away.
+    fn update_forced(&mut self, elf: usize) {
+        let total = self.load[elf] as u128 + self.rest[elf] as u128;
+        let mut excess = total.saturating_sub(self.threshold as u128);
+        let mut forced = 0;
+        for &item in &self.own[elf][self.placed[elf]..] {
+            if excess == 0 {
+                break;
+            }
+            excess = excess.saturating_sub(item as u128);
+            forced += 1;
+        }
+        self.bound = self.bound - self.forced[elf] + forced;
+        self.forced[elf] = forced;
+    }
+}
+
+/// Overloaded elves give away their heaviest items, which are then placed
+/// heaviest first on the elf with the least room that still fits them.
+fn greedy(items: &[(u64, usize)], totals: &[u64], threshold: u64) -> Option<Vec<usize>> {
+    let mut targets: Vec<usize> = items.iter().map(|&(_, owner)| owner).collect();
+    let mut load = totals.to_vec();
+    let mut shed = vec![];
+    for (idx, &(item, owner)) in items.iter().enumerate() {
+        if load[owner] > threshold {
+            load[owner] -= item;
+            shed.push(idx);
+        }
+    }
+
+    for idx in shed {
+        let (item, owner) = items[idx];
+        let best = (0..load.len())
+            .filter(|&elf| elf != owner && load[elf] <= threshold && item <= threshold - load[elf])
+            .min_by_key(|&elf| threshold - load[elf])?;
+        load[best] += item;
+        targets[idx] = best;
+    }
+    return Some(targets);
+}
+
+fn count_moved(items: &[(u64, usize)], targets: &[usize]) -> usize {
+    return items
+        .iter()
+        .zip(targets)
+        .filter(|&(&(_, owner), &target)| owner != target)
+        .count();
+}
+
+/// Applies `moves` to a copy of the elves and checks the result is under `threshold`.
+pub fn validate(elves: &[Elf], moves: &[Move], threshold: u64) -> Result<Vec<Elf>, RebalanceError> {
+    let mut elves = elves.to_vec();
+    let position = |elves: &[Elf], id: usize| elves.iter().position(|elf| elf.id == id);
+
+    for mv in moves {
+        let from = position(&elves, mv.from).ok_or(RebalanceError::MissingItem(*mv))?;
+        let to = position(&elves, mv.to).ok_or(RebalanceError::MissingItem(*mv))?;
+        let item_idx = elves[from]
+            .items
+            .iter()
+            .position(|&item| item == mv.item)
+            .ok_or(RebalanceError::MissingItem(*mv))?;
+
+        let item = elves[from].items.remove(item_idx);
+        elves[to].items.push(item);
+    }
+
+    for elf in &elves {
+        let total = answer::sum(elf.items.iter().copied())?;
+        if total > threshold {
+            return Err(RebalanceError::OverThreshold { elf: elf.id, total });
+        }
+    }
+
+    return Ok(elves);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::parse_elves;
+
+    const TEST_INPUT: &str = include_str!("../../test_inputs/day1.test.txt");
+
+    fn elves(items: &[&[u64]]) -> Vec<Elf> {
+        return items
+            .iter()
+            .enumerate()
+            .map(|(idx, items)| Elf {
+                id: idx + 1,
+                items: items.to_vec(),
+            })
+            .collect();
+    }
+
+    #[test]
+    fn plans_minimal_moves() {
+        let elves = parse_elves(TEST_INPUT).unwrap();
+        let plan = plan(&elves, 15000, 1000).unwrap();
+
+        // elf 4 carries 7000 + 8000 + 9000 and only has to give away the 9000
+        assert_eq!(
+            plan.moves,
+            vec![Move {
+                item: 9000,
+                from: 4,
+                to: 1
+            }]
+        );
+        assert!(plan.optimal);
+        let balanced = validate(&elves, &plan.moves, 15000).unwrap();
+        assert_eq!(balanced[0].items, vec![1000, 2000, 3000, 9000]);
+    }
+
+    #[test]
+    fn moves_items_off_elves_under_the_threshold() {
+        // neither 5 has room for a 6 until one of them moves over to the other
+        let elves = elves(&[&[6, 6], &[5], &[5]]);
+        let plan = plan(&elves, 10, 1000).unwrap();
+        assert_eq!(plan.moves.len(), 2);
+        assert!(plan.optimal);
+        validate(&elves, &plan.moves, 10).unwrap();
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut seed: u64 = 7;
+        let mut next = |n: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            return (seed >> 33) % n;
+        };
+
+        for _ in 0..200 {
+            let items: Vec<Vec<u64>> = (0..4)
+                .map(|_| (0..next(4)).map(|_| 1 + next(9)).collect())
+                .collect();
+            let elves: Vec<Elf> = items
+                .iter()
+                .enumerate()
+                .map(|(idx, items)| Elf {
+                    id: idx + 1,
+                    items: items.clone(),
+                })
+                .collect();
+            let threshold = 8 + next(8);
+
+            let flat: Vec<(u64, usize)> = items
+                .iter()
+                .enumerate()
+                .flat_map(|(elf, items)| items.iter().map(move |&item| (item, elf)))
+                .collect();
+            let expected = brute_force(&flat, &mut vec![0; 4], threshold, 0);
+
+            match plan(&elves, threshold, usize::MAX) {
+                Ok(plan) => {
+                    assert_eq!(Some(plan.moves.len()), expected);
+                    validate(&elves, &plan.moves, threshold).unwrap();
+                }
+                Err(_) => assert_eq!(expected, None),
+            }
+        }
+    }
+
+    /// Fewest items moved, trying every elf for every item.
+    fn brute_force(
+        items: &[(u64, usize)],
+        load: &mut Vec<u64>,
+        threshold: u64,
+        idx: usize,
+    ) -> Option<usize> {
+        let Some(&(item, owner)) = items.get(idx) else {
+            return Some(0);
+        };
+        let mut best = None;
+        for elf in 0..load.len() {
+            if load[elf] + item > threshold {
+                continue;
+            }
+            load[elf] += item;
+            if let Some(moved) = brute_force(items, load, threshold, idx + 1) {
+                let moved = moved + (elf != owner) as usize;
+                best = Some(best.map_or(moved, |best: usize| best.min(moved)));
+            }
+            load[elf] -= item;
+        }
+        return best;
+    }
+
+    #[test]
+    fn reports_impossible_plans() {
+        let elves = parse_elves(TEST_INPUT).unwrap();
+        assert_eq!(
+            plan(&elves, 8000, 1000),
+            Err(RebalanceError::ItemTooHeavy { elf: 4, item: 9000 })
+        );
+        assert_eq!(plan(&elves, 10000, 1000), Err(RebalanceError::NoRoom));
+        assert_eq!(
+            validate(&elves, &[], 12000),
+            Err(RebalanceError::OverThreshold {
+                elf: 4,
+                total: 24000
+            })
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::game::{Game, Play};
+use crate::Outcome;
+use serde::Serialize;
+
+/// A decoded round of the guide and how it played out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoredRound {
+    pub line: usize,
+    pub opponent: Play,
+    pub play: Play,
+    pub outcome: Outcome,
+}
+
+impl ScoredRound {
+    pub fn score(&self) -> i32 {
+        return self.play.score() + self.outcome.score();
+    }
+}
+
+/// One audit line, with the plays spelled out.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Row {
+    pub line: usize,
+    pub opponent: String,
+    pub play: String,
+    pub outcome: Outcome,
+    pub shape_score: i32,
+    pub outcome_score: i32,
+    pub score: i32,
+}
+
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct Breakdown {
+    pub rounds: Vec<Row>,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub total: i32,
+}
+
+impl Breakdown {
+    pub fn new(game: &Game, rounds: &[ScoredRound]) -> Self {
+        let count = |outcome| rounds.iter().filter(|r| r.outcome == outcome).count();
+
+        return Self {
+            rounds: rounds
+                .iter()
+                .map(|round| Row {
+                    line: round.line,
+                    opponent: game.name(round.opponent).to_string(),
+                    play: game.name(round.play).to_string(),
+                    outcome: round.outcome,
+                    shape_score: round.play.score(),
+                    outcome_score: round.outcome.score(),
+                    score: round.score(),
+                })
+                .collect(),
+            wins: count(Outcome::Win),
+            draws: count(Outcome::Draw),
+            losses: count(Outcome::Lose),
+            total: rounds.iter().map(ScoredRound::score).sum(),
+        };
+    }
+
+    /// The rounds as CSV with a header line. Aggregates are left to the caller,
+    /// so the output stays a plain table.
+    pub fn to_csv(&self) -> String {
+        let mut csv = "line,opponent,play,outcome,shape_score,outcome_score,score\n".to_string();
+        for row in &self.rounds {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                row.line,
+                csv_field(&row.opponent),
+                csv_field(&row.play),
+                format!("{:?}", row.outcome).to_lowercase(),
+                row.shape_score,
+                row.outcome_score,
+                row.score
+            ));
+        }
+        return csv;
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string_pretty(self).expect("Breakdown is always valid JSON");
+    }
+}
+
+/// Quotes a field holding a comma, quote or line break, doubling inner quotes
+/// (RFC 4180). Move names come from the config, so they may hold any of these.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", field.replace('"', "\"\""));
+    }
+    return field.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::ROCK_PAPER_SCISSORS;
+
+    fn example() -> (Game, Vec<ScoredRound>) {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let rounds = [(0, 1), (1, 0), (2, 2)]
+            .iter()
+            .enumerate()
+            .map(|(idx, &(opp, me))| ScoredRound {
+                line: idx + 1,
+                opponent: Play(opp),
+                play: Play(me),
+                outcome: game.evaluate(Play(opp), Play(me)),
+            })
+            .collect();
+        return (game, rounds);
+    }
+
+    #[test]
+    fn tallies_part_one_example() {
+        let (game, rounds) = example();
+        let breakdown = Breakdown::new(&game, &rounds);
+
+        assert_eq!(
+            (breakdown.wins, breakdown.draws, breakdown.losses),
+            (1, 1, 1)
+        );
+        assert_eq!(breakdown.total, 15);
+        assert_eq!(
+            breakdown.to_csv(),
+            "line,opponent,play,outcome,shape_score,outcome_score,score\n\
+             1,rock,paper,win,2,6,8\n\
+             2,paper,rock,lose,1,0,1\n\
+             3,scissors,scissors,draw,3,3,6\n"
+        );
+    }
+
+    #[test]
+    fn quotes_awkward_move_names() {
+        let game = Game::cyclic(&["rock, hard", "say \"paper\"", "scissors"]).unwrap();
+        let round = ScoredRound {
+            line: 1,
+            opponent: Play(0),
+            play: Play(1),
+            outcome: Outcome::Win,
+        };
+
+        assert_eq!(
+            Breakdown::new(&game, &[round]).to_csv().lines().nth(1),
+            Some("1,\"rock, hard\",\"say \"\"paper\"\"\",win,2,6,8")
+        );
+    }
+
+    #[test]
+    fn serialises_to_json() {
+        let (game, rounds) = example();
+        let json: serde_json::Value =
+            serde_json::from_str(&Breakdown::new(&game, &rounds).to_json()).unwrap();
+
+        assert_eq!(json["total"], 15);
+        assert_eq!(json["rounds"][0]["play"], "paper");
+        assert_eq!(json["rounds"][0]["outcome"], "win");
+        assert_eq!(json["rounds"][1]["outcome_score"], 0);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{Outcome, UnknownSymbol};
+use std::fmt::{self, Display};
+
+pub const ROCK_PAPER_SCISSORS: [&str; 3] = ["rock", "paper", "scissors"];
+
+/// A move, identified by its position in the game's list of moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Play(pub usize);
+
+impl Play {
+    /// The first move scores 1, the second 2, and so on.
+    pub fn score(&self) -> i32 {
+        return self.0 as i32 + 1;
+    }
+}
+
+/// Looks a move up by name in the game, ignoring case.
+impl TryFrom<(&Game, &str)> for Play {
+    type Error = UnknownSymbol;
+
+    fn try_from((game, name): (&Game, &str)) -> Result<Self, Self::Error> {
+        return game
+            .moves
+            .iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(name))
+            .map(Play)
+            .ok_or_else(|| UnknownSymbol(name.to_string()));
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GameError {
+    /// Cyclic games can't be balanced over an even number of moves
+    EvenMoves(usize),
+    /// The same name, ignoring case, is given to two moves
+    Duplicate(String),
+}
+
+impl Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EvenMoves(n) => {
+                write!(f, "a cyclic game needs an odd number of moves, got {}", n)
+            }
+            Self::Duplicate(name) => write!(f, "{:?} is listed twice", name),
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
+
+/// A set of moves and which move beats which.
+#[derive(Debug, Clone)]
+pub struct Game {
+    moves: Vec<String>,
+    beats: Vec<Vec<bool>>,
+}
+
+impl Game {
+    /// Builds a game from `(winner, loser)` pairs. Pairs in neither direction draw.
+    pub fn new(moves: Vec<String>, beats: &[(usize, usize)]) -> Self {
+        let mut table = vec![vec![false; moves.len()]; moves.len()];
+        for &(winner, loser) in beats {
+            assert!(
+                !table[loser][winner],
+                "{} and {} beat each other",
+                winner, loser
+            );
+            table[winner][loser] = true;
+        }
+        return Self {
+            moves,
+            beats: table,
+        };
+    }
+
+    /// Balanced game over an odd number of moves where each move beats the
+    /// moves an odd number of steps before it (wrapping around). With three
+    /// moves that is Rock-Paper-Scissors; with `rock, paper, scissors, spock,
+    /// lizard` it is Rock-Paper-Scissors-Lizard-Spock.
+    pub fn cyclic<S: AsRef<str>>(moves: &[S]) -> Result<Self, GameError> {
+        let n = moves.len();
+        if n.is_multiple_of(2) {
+            return Err(GameError::EvenMoves(n));
+        }
+        for (idx, name) in moves.iter().enumerate() {
+            let name = name.as_ref();
+            if moves[..idx]
+                .iter()
+                .any(|other| other.as_ref().eq_ignore_ascii_case(name))
+            {
+                return Err(GameError::Duplicate(name.to_string()));
+            }
+        }
+
+        let beats: Vec<(usize, usize)> = (0..n)
+            .flat_map(|winner| {
+                return (0..n)
+                    .filter(move |loser| (winner + n - loser) % n % 2 == 1)
+                    .map(move |loser| (winner, loser));
+            })
+            .collect();
+        let moves = moves.iter().map(|name| name.as_ref().to_string()).collect();
+
+        return Ok(Self::new(moves, &beats));
+    }
+
+    pub fn plays(&self) -> impl Iterator<Item = Play> {
+        return (0..self.moves.len()).map(Play);
+    }
+
+    pub fn name(&self, play: Play) -> &str {
+        return &self.moves[play.0];
+    }
+
+    pub fn evaluate(&self, opp: Play, me: Play) -> Outcome {
+        if self.beats[me.0][opp.0] {
+            return Outcome::Win;
+        } else if self.beats[opp.0][me.0] {
+            return Outcome::Lose;
+        } else {
+            return Outcome::Draw;
+        }
+    }
+
+    /// The lowest scoring play that gets `outcome` against `opp`, if any does.
+    pub fn find_play(&self, opp: Play, outcome: Outcome) -> Option<Play> {
+        return self.plays().find(|&me| self.evaluate(opp, me) == outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn cyclic_three_is_rock_paper_scissors() {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let [rock, paper, scissors] = [Play(0), Play(1), Play(2)];
+
+        assert_eq!(game.evaluate(scissors, rock), Outcome::Win);
+        assert_eq!(game.evaluate(rock, paper), Outcome::Win);
+        assert_eq!(game.evaluate(paper, scissors), Outcome::Win);
+        assert_eq!(game.evaluate(rock, scissors), Outcome::Lose);
+        assert_eq!(game.evaluate(paper, paper), Outcome::Draw);
+        assert_eq!(game.find_play(rock, Outcome::Lose), Some(scissors));
+    }
+
+    #[test]
+    fn cyclic_five_is_rock_paper_scissors_lizard_spock() {
+        let game = Game::cyclic(&["rock", "paper", "scissors", "spock", "lizard"]).unwrap();
+        let play = |name| Play::try_from((&game, name)).unwrap();
+
+        for (winner, loser) in [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ] {
+            assert_eq!(game.evaluate(play(loser), play(winner)), Outcome::Win);
+            assert_eq!(game.evaluate(play(winner), play(loser)), Outcome::Lose);
+        }
+        assert_eq!(play("Lizard").score(), 5);
+        assert!(Play::try_from((&game, "well")).is_err());
+    }
+
+    #[test]
+    fn rejects_unbalanced_games() {
+        assert_eq!(
+            Game::cyclic(&["rock", "paper"]).unwrap_err(),
+            GameError::EvenMoves(2)
+        );
+        assert_eq!(
+            Game::cyclic(&["rock", "paper", "Rock"]).unwrap_err(),
+            GameError::Duplicate("Rock".to_string())
+        );
+    }
+}
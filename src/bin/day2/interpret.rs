@@ -0,0 +1,168 @@
+use crate::game::{Game, Play};
+use crate::Outcome;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+/// What the second column of the guide is taken to mean.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reading {
+    Plays(Vec<(String, Play)>),
+    Outcomes(Vec<(String, Outcome)>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    pub reading: Reading,
+    pub score: i32,
+}
+
+impl Interpretation {
+    pub fn describe(&self, game: &Game) -> String {
+        let pairs: Vec<String> = match &self.reading {
+            Reading::Plays(mapping) => mapping
+                .iter()
+                .map(|(symbol, play)| format!("{}={}", symbol, game.name(*play)))
+                .collect(),
+            Reading::Outcomes(mapping) => mapping
+                .iter()
+                .map(|(symbol, outcome)| format!("{}={:?}", symbol, outcome))
+                .collect(),
+        };
+        return pairs.join(" ");
+    }
+}
+
+impl Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Plays(_) => f.pad("plays"),
+            Self::Outcomes(_) => f.pad("outcomes"),
+        }
+    }
+}
+
+/// Scores the guide under every way of reading its second column, best first.
+///
+/// The second column's symbols are mapped one-to-one onto the game's plays (every
+/// permutation) and onto the three outcomes. Rounds are tallied per `(opponent,
+/// symbol)` up front, so each interpretation costs one pass over that tally rather
+/// than over the whole guide.
+pub fn search(game: &Game, rounds: &[(Play, &str)]) -> Vec<Interpretation> {
+    let mut tally: BTreeMap<(Play, &str), i32> = BTreeMap::new();
+    for &(opp, symbol) in rounds {
+        *tally.entry((opp, symbol)).or_default() += 1;
+    }
+
+    let mut symbols: Vec<&str> = rounds.iter().map(|&(_, symbol)| symbol).collect();
+    symbols.sort();
+    symbols.dedup();
+
+    let mut interpretations = vec![];
+
+    let plays: Vec<Play> = game.plays().collect();
+    for assignment in permutations(&plays, symbols.len()) {
+        let score = tally
+            .iter()
+            .map(|(&(opp, symbol), count)| {
+                let me = assignment[symbols.binary_search(&symbol).unwrap()];
+                return count * (game.evaluate(opp, me).score() + me.score());
+            })
+            .sum();
+        let mapping = symbols
+            .iter()
+            .map(|s| s.to_string())
+            .zip(assignment)
+            .collect();
+        interpretations.push(Interpretation {
+            reading: Reading::Plays(mapping),
+            score,
+        });
+    }
+
+    for assignment in permutations(&Outcome::ALL, symbols.len()) {
+        let score: Option<i32> = tally
+            .iter()
+            .map(|(&(opp, symbol), count)| {
+                let outcome = assignment[symbols.binary_search(&symbol).unwrap()];
+                let me = game.find_play(opp, outcome)?;
+                return Some(count * (outcome.score() + me.score()));
+            })
+            .sum();
+        // skip readings that ask for an outcome no move can get
+        if let Some(score) = score {
+            let mapping = symbols
+                .iter()
+                .map(|s| s.to_string())
+                .zip(assignment)
+                .collect();
+            interpretations.push(Interpretation {
+                reading: Reading::Outcomes(mapping),
+                score,
+            });
+        }
+    }
+
+    interpretations.sort_by_key(|interpretation| Reverse(interpretation.score));
+    return interpretations;
+}
+
+/// Every ordered choice of `k` distinct items.
+fn permutations<T: Copy>(items: &[T], k: usize) -> Vec<Vec<T>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+
+    let mut all = vec![];
+    for (idx, &first) in items.iter().enumerate() {
+        let mut rest = items.to_vec();
+        rest.remove(idx);
+        for mut tail in permutations(&rest, k - 1) {
+            tail.insert(0, first);
+            all.push(tail);
+        }
+    }
+    return all;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::ROCK_PAPER_SCISSORS;
+
+    #[test]
+    fn ranks_every_reading() {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let [rock, paper, scissors] = [Play(0), Play(1), Play(2)];
+        let rounds = [(rock, "Y"), (paper, "X"), (scissors, "Z")];
+
+        let interpretations = search(&game, &rounds);
+        assert_eq!(interpretations.len(), 12);
+
+        let best = interpretations.first().unwrap();
+        assert_eq!(best.score, 24);
+        assert_eq!(best.describe(&game), "X=scissors Y=paper Z=rock");
+
+        let worst = interpretations.last().unwrap();
+        assert_eq!(worst.score, 6);
+        assert_eq!(worst.describe(&game), "X=rock Y=scissors Z=paper");
+
+        // the puzzle's own two readings
+        let score_of = |description: &str| {
+            return interpretations
+                .iter()
+                .find(|i| i.describe(&game) == description)
+                .map(|i| i.score);
+        };
+        assert_eq!(score_of("X=rock Y=paper Z=scissors"), Some(15));
+        assert_eq!(score_of("X=Lose Y=Draw Z=Win"), Some(12));
+    }
+
+    #[test]
+    fn permutes_without_repeats() {
+        assert_eq!(permutations(&[1, 2, 3], 3).len(), 6);
+        assert_eq!(permutations(&[1, 2, 3], 2).len(), 6);
+        assert_eq!(permutations(&[1, 2], 3).len(), 0);
+    }
+}
@@ -0,0 +1,552 @@
+use advent_of_code::config::{self, Validate};
+use advent_of_code::{cli::Args, trace};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+mod breakdown;
+mod game;
+mod interpret;
+mod optimise;
+mod simulate;
+
+use breakdown::{Breakdown, ScoredRound};
+use game::{Game, Play, ROCK_PAPER_SCISSORS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct UnknownSymbol(pub String);
+
+impl Display for UnknownSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown symbol {:?}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownSymbol {}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ParseError {
+    line: usize,
+    reason: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl TryFrom<&str> for Outcome {
+    type Error = UnknownSymbol;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "lose" => return Ok(Self::Lose),
+            "draw" => return Ok(Self::Draw),
+            "win" => return Ok(Self::Win),
+            _ => return Err(UnknownSymbol(s.to_string())),
+        }
+    }
+}
+
+impl Outcome {
+    const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+    fn score(&self) -> i32 {
+        match self {
+            Self::Win => 6,
+            Self::Draw => 3,
+            Self::Lose => 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct Config {
+    /// Moves of a cyclic game, each beating the moves an odd number of steps before it
+    moves: Vec<String>,
+    #[serde(flatten)]
+    codec: StrategyCodec,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Self {
+            moves: ROCK_PAPER_SCISSORS.map(String::from).to_vec(),
+            codec: StrategyCodec::default(),
+        };
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        return Game::cyclic(&self.moves)
+            .map(|_| ())
+            .map_err(|err| err.to_string());
+    }
+}
+
+/// How the symbols of a strategy guide map to plays and outcomes.
+///
+/// Each table maps a guide symbol to the name of a move of the game (`rock`,
+/// `paper`, ...) or an outcome (`lose`, `draw`, `win`). Symbols missing from a
+/// table are parsed as they are, so guides written with full words need no mapping.
+/// Tables in the config file add to the default ones rather than replacing them.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct StrategyCodec {
+    /// First column
+    opponent: BTreeMap<String, String>,
+    /// Second column read as our play (part one)
+    response: BTreeMap<String, String>,
+    /// Second column read as the outcome to aim for (part two)
+    outcome: BTreeMap<String, String>,
+}
+
+impl Default for StrategyCodec {
+    fn default() -> Self {
+        let table = |pairs: [(&str, &str); 3]| {
+            return pairs
+                .iter()
+                .map(|(symbol, name)| (symbol.to_string(), name.to_string()))
+                .collect();
+        };
+
+        return Self {
+            opponent: table([("A", "rock"), ("B", "paper"), ("C", "scissors")]),
+            response: table([("X", "rock"), ("Y", "paper"), ("Z", "scissors")]),
+            outcome: table([("X", "lose"), ("Y", "draw"), ("Z", "win")]),
+        };
+    }
+}
+
+impl StrategyCodec {
+    fn lookup<'a>(table: &'a BTreeMap<String, String>, symbol: &'a str) -> &'a str {
+        return table
+            .get(symbol)
+            .map(|name| name.as_str())
+            .unwrap_or(symbol);
+    }
+
+    fn opponent(&self, game: &Game, symbol: &str) -> Result<Play, UnknownSymbol> {
+        return Play::try_from((game, Self::lookup(&self.opponent, symbol)));
+    }
+
+    fn response(&self, game: &Game, symbol: &str) -> Result<Play, UnknownSymbol> {
+        return Play::try_from((game, Self::lookup(&self.response, symbol)));
+    }
+
+    fn outcome(&self, symbol: &str) -> Result<Outcome, UnknownSymbol> {
+        return Outcome::try_from(Self::lookup(&self.outcome, symbol));
+    }
+}
+
+fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day2");
+    let config: Config = config::load(&args, "day2").expect("Invalid config");
+    let game = Game::cyclic(&config.moves).expect("Invalid config");
+    let file_data = std::fs::read_to_string("src/inputs/day2.txt").expect("Failed to read the file!");
+
+    if args.flag("--interpretations") {
+        print_interpretations(&file_data, &game, &config.codec);
+        return;
+    }
+
+    if args.flag("--simulate") {
+        run_simulation(&args, &file_data, &game, &config.codec);
+        return;
+    }
+
+    if args.flag("--optimise") {
+        print_optimal(&args, &file_data, &game, &config.codec);
+        return;
+    }
+
+    if args.flag("--breakdown") {
+        print_breakdown(&args, &file_data, &game, &config.codec);
+        return;
+    }
+
+    let first_score =
+        calc_first_score(&file_data, &game, &config.codec).expect("Invalid strategy guide");
+    let second_score =
+        calc_second_score(&file_data, &game, &config.codec).expect("Invalid strategy guide");
+
+    dbg!(first_score);
+    dbg!(second_score);
+}
+
+fn print_interpretations(file_data: &str, game: &Game, codec: &StrategyCodec) {
+    let rounds = decode_opponents(file_data, game, codec).expect("Invalid strategy guide");
+    let interpretations = interpret::search(game, &rounds);
+
+    for interpretation in &interpretations {
+        println!(
+            "{:>8}  {:<8}  {}",
+            interpretation.score,
+            interpretation.reading,
+            interpretation.describe(game)
+        );
+    }
+    if let (Some(best), Some(worst)) = (interpretations.first(), interpretations.last()) {
+        println!("Best: {} ({})", best.describe(game), best.score);
+        println!("Worst: {} ({})", worst.describe(game), worst.score);
+    }
+}
+
+/// Plays our strategy (`--player`, default the part one guide) against an opponent
+/// strategy (`--against`, default the guide's first column) over seeded matches.
+fn run_simulation(args: &Args, file_data: &str, game: &Game, codec: &StrategyCodec) {
+    let number = |flag: &str, default: usize| {
+        return args.value(flag).map_or(default, |value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("{} is not a number", flag))
+        });
+    };
+
+    let guide = parse_guide(file_data).expect("Invalid strategy guide");
+    let mut opponent_guide = vec![];
+    let mut response_guide = vec![];
+    for (line, opp, me) in guide {
+        opponent_guide.push(
+            codec
+                .opponent(game, opp)
+                .map_err(decode_error(line))
+                .unwrap(),
+        );
+        response_guide.push(
+            codec
+                .response(game, me)
+                .map_err(decode_error(line))
+                .unwrap(),
+        );
+    }
+
+    let strategies = "guide, random, frequency or beat-last";
+    let mut me = simulate::strategy(args.value("--player").unwrap_or("guide"), &response_guide)
+        .unwrap_or_else(|| panic!("--player must be one of {}", strategies));
+    let mut opponent =
+        simulate::strategy(args.value("--against").unwrap_or("guide"), &opponent_guide)
+            .unwrap_or_else(|| panic!("--against must be one of {}", strategies));
+
+    let rounds = number("--rounds", response_guide.len());
+    let matches = number("--matches", 100);
+    let seed = number("--seed", 0) as u64;
+    let summary = simulate::simulate(game, me.as_mut(), opponent.as_mut(), rounds, matches, seed);
+
+    println!(
+        "{} vs {}: {} matches of {} rounds, seed {}",
+        me.name(),
+        opponent.name(),
+        matches,
+        rounds,
+        seed
+    );
+    println!(
+        "wins {} / draws {} / losses {} (win rate {:.1}%)",
+        summary.wins,
+        summary.draws,
+        summary.losses,
+        summary.win_rate() * 100.0
+    );
+    for percentile in [0, 25, 50, 75, 100] {
+        if let Some(score) = summary.percentile(percentile) {
+            println!("p{:<3} score {}", percentile, score);
+        }
+    }
+}
+
+/// Prints every round of part one (or `--part 2`) as CSV, or as JSON with
+/// `--format json`. The CSV aggregates go to stderr so the table can be piped.
+fn print_breakdown(args: &Args, file_data: &str, game: &Game, codec: &StrategyCodec) {
+    let rounds = match args.value("--part").unwrap_or("1") {
+        "1" => score_first_rounds(file_data, game, codec),
+        "2" => score_second_rounds(file_data, game, codec),
+        part => panic!("--part must be 1 or 2, not {}", part),
+    }
+    .expect("Invalid strategy guide");
+    let breakdown = Breakdown::new(game, &rounds);
+
+    match args.value("--format").unwrap_or("csv") {
+        "csv" => {
+            print!("{}", breakdown.to_csv());
+            eprintln!(
+                "wins {} / draws {} / losses {}, total {}",
+                breakdown.wins, breakdown.draws, breakdown.losses, breakdown.total
+            );
+        }
+        "json" => println!("{}", breakdown.to_json()),
+        format => panic!("--format must be csv or json, not {}", format),
+    }
+}
+
+/// Finds our best plays against the guide's first column, optionally limited by
+/// `--max-uses N` (each play) or `--max-losses K`.
+fn print_optimal(args: &Args, file_data: &str, game: &Game, codec: &StrategyCodec) {
+    let limit = |flag: &str| {
+        return args.value(flag).map(|value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("{} is not a number", flag))
+        });
+    };
+    let constraint = match (limit("--max-uses"), limit("--max-losses")) {
+        (None, None) => optimise::Constraint::Unlimited,
+        (Some(uses), None) => optimise::Constraint::MaxUses(uses),
+        (None, Some(losses)) => optimise::Constraint::MaxLosses(losses),
+        (Some(_), Some(_)) => panic!("--max-uses and --max-losses can't be combined"),
+    };
+
+    let rounds = decode_opponents(file_data, game, codec).expect("Invalid strategy guide");
+    let opponents: Vec<Play> = rounds.iter().map(|&(opp, _)| opp).collect();
+    let Some(plan) = optimise::optimise(game, &opponents, constraint) else {
+        println!("No plays satisfy {:?}", constraint);
+        return;
+    };
+
+    let scored: Vec<ScoredRound> = opponents
+        .iter()
+        .zip(&plan.plays)
+        .enumerate()
+        .map(|(idx, (&opp, &me))| ScoredRound {
+            line: idx + 1,
+            opponent: opp,
+            play: me,
+            outcome: game.evaluate(opp, me),
+        })
+        .collect();
+    let breakdown = Breakdown::new(game, &scored);
+
+    println!("Best score under {:?}: {}", constraint, plan.score);
+    println!(
+        "wins {} / draws {} / losses {}",
+        breakdown.wins, breakdown.draws, breakdown.losses
+    );
+    for play in game.plays() {
+        let uses = plan.plays.iter().filter(|&&me| me == play).count();
+        println!("{:<10} {}", game.name(play), uses);
+    }
+}
+
+/// Splits the guide into its two columns, skipping blank lines.
+fn parse_guide(file_data: &str) -> Result<Vec<(usize, &str, &str)>, ParseError> {
+    return file_data
+        .lines()
+        .enumerate()
+        .filter(|(_, game)| !game.trim().is_empty())
+        .map(|(idx, game)| {
+            let (first, second) = game.trim().split_once(' ').ok_or(ParseError {
+                line: idx + 1,
+                reason: format!("expected two columns in {:?}", game),
+            })?;
+            return Ok((idx + 1, first, second.trim()));
+        })
+        .collect();
+}
+
+/// Decodes only the opponent column, leaving our column as written.
+fn decode_opponents<'a>(
+    file_data: &'a str,
+    game: &Game,
+    codec: &StrategyCodec,
+) -> Result<Vec<(Play, &'a str)>, ParseError> {
+    return parse_guide(file_data)?
+        .into_iter()
+        .map(|(line, opp, second)| {
+            let opp = codec.opponent(game, opp).map_err(decode_error(line))?;
+            return Ok((opp, second));
+        })
+        .collect();
+}
+
+fn decode_error(line: usize) -> impl Fn(UnknownSymbol) -> ParseError {
+    return move |err| ParseError {
+        line,
+        reason: err.to_string(),
+    };
+}
+
+/// Reads the second column as our play.
+fn score_first_rounds(
+    file_data: &str,
+    game: &Game,
+    codec: &StrategyCodec,
+) -> Result<Vec<ScoredRound>, ParseError> {
+    return parse_guide(file_data)?
+        .into_iter()
+        .map(|(line, opp, me)| {
+            let opp = codec.opponent(game, opp).map_err(decode_error(line))?;
+            let me = codec.response(game, me).map_err(decode_error(line))?;
+
+            return Ok(ScoredRound {
+                line,
+                opponent: opp,
+                play: me,
+                outcome: game.evaluate(opp, me),
+            });
+        })
+        .collect();
+}
+
+/// Reads the second column as the outcome to aim for.
+fn score_second_rounds(
+    file_data: &str,
+    game: &Game,
+    codec: &StrategyCodec,
+) -> Result<Vec<ScoredRound>, ParseError> {
+    return parse_guide(file_data)?
+        .into_iter()
+        .map(|(line, opp, outcome)| {
+            let opp = codec.opponent(game, opp).map_err(decode_error(line))?;
+            let outcome = codec.outcome(outcome).map_err(decode_error(line))?;
+
+            let me = game.find_play(opp, outcome).ok_or_else(|| ParseError {
+                line,
+                reason: format!("no move gets {:?} against {}", outcome, game.name(opp)),
+            })?;
+
+            return Ok(ScoredRound {
+                line,
+                opponent: opp,
+                play: me,
+                outcome,
+            });
+        })
+        .collect();
+}
+
+#[tracing::instrument(skip_all)]
+fn calc_first_score(
+    file_data: &str,
+    game: &Game,
+    codec: &StrategyCodec,
+) -> Result<i32, ParseError> {
+    let rounds = score_first_rounds(file_data, game, codec)?;
+    return Ok(rounds.iter().map(ScoredRound::score).sum());
+}
+
+#[tracing::instrument(skip_all)]
+fn calc_second_score(
+    file_data: &str,
+    game: &Game,
+    codec: &StrategyCodec,
+) -> Result<i32, ParseError> {
+    let rounds = score_second_rounds(file_data, game, codec)?;
+    return Ok(rounds.iter().map(ScoredRound::score).sum());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../test_inputs/day2.test.txt");
+
+    #[test]
+    fn check_first_score() {
+        assert_eq!(
+            calc_first_score(
+                TEST_INPUT,
+                &Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap(),
+                &StrategyCodec::default()
+            ),
+            Ok(15)
+        );
+    }
+
+    #[test]
+    fn check_second_score() {
+        assert_eq!(
+            calc_second_score(
+                TEST_INPUT,
+                &Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap(),
+                &StrategyCodec::default()
+            ),
+            Ok(12)
+        );
+    }
+
+    #[test]
+    fn scores_rock_paper_scissors_lizard_spock() {
+        let game = Game::cyclic(&["rock", "paper", "scissors", "spock", "lizard"]).unwrap();
+        let mut codec = StrategyCodec::default();
+        codec.opponent.insert("D".to_string(), "spock".to_string());
+        codec.response.insert("V".to_string(), "lizard".to_string());
+
+        // lizard poisons spock, scissors decapitate lizard
+        assert_eq!(
+            calc_first_score("D V\nlizard Z", &game, &codec),
+            Ok(6 + 5 + 6 + 3)
+        );
+        assert_eq!(calc_second_score("D Z", &game, &codec), Ok(6 + 2));
+    }
+
+    #[test]
+    fn config_tables_add_to_the_defaults() {
+        let path = std::env::temp_dir().join("day2-config-tables.toml");
+        std::fs::write(
+            &path,
+            "[day2]\nmoves = [\"rock\", \"paper\", \"scissors\", \"spock\", \"lizard\"]\n\n\
+             [day2.opponent]\nD = \"spock\"\n",
+        )
+        .unwrap();
+        let args = Args::new(["--config", path.to_str().unwrap()]);
+        let config: Config = config::load(&args, "day2").unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // spock smashes scissors, and A still means rock
+        let game = Game::cyclic(&config.moves).unwrap();
+        assert_eq!(
+            calc_first_score("D Z\nA Y", &game, &config.codec),
+            Ok(3 + 2 + 6)
+        );
+    }
+
+    #[test]
+    fn outcomes_come_from_the_codec() {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let codec = StrategyCodec {
+            outcome: [("L".to_string(), "lose".to_string())].into(),
+            ..StrategyCodec::default()
+        };
+        assert_eq!(calc_second_score("A L\nA win", &game, &codec), Ok(3 + 8));
+        assert_eq!(
+            calc_second_score("A X", &game, &codec),
+            Err(ParseError {
+                line: 1,
+                reason: "unknown symbol \"X\"".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn decodes_custom_symbols() {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let mut codec = StrategyCodec::default();
+        codec.response.insert("P".to_string(), "paper".to_string());
+        assert_eq!(
+            calc_first_score("A P\nB rock\nscissors Z", &game, &codec),
+            Ok(15)
+        );
+        assert_eq!(
+            calc_first_score("A Y\nB Q", &game, &codec),
+            Err(ParseError {
+                line: 2,
+                reason: "unknown symbol \"Q\"".to_string()
+            })
+        );
+    }
+}
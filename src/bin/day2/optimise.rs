@@ -0,0 +1,320 @@
+use crate::game::{Game, Play};
+use crate::Outcome;
+
+/// What the optimal counter-strategy may not exceed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Constraint {
+    Unlimited,
+    /// Each play at most this many times
+    MaxUses(usize),
+    /// At most this many lost rounds
+    MaxLosses(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub plays: Vec<Play>,
+    pub score: i32,
+}
+
+fn round_score(game: &Game, opp: Play, me: Play) -> i32 {
+    return game.evaluate(opp, me).score() + me.score();
+}
+
+/// Our best-scoring plays against a known sequence of opponent plays, or `None`
+/// if the constraint can't be met (e.g. too few uses to cover every round).
+pub fn optimise(game: &Game, opponents: &[Play], constraint: Constraint) -> Option<Plan> {
+    let plays = match constraint {
+        Constraint::Unlimited => Some(best_per_round(game, opponents)),
+        Constraint::MaxUses(uses) => with_max_uses(game, opponents, uses),
+        Constraint::MaxLosses(losses) => with_max_losses(game, opponents, losses),
+    }?;
+
+    let score = opponents
+        .iter()
+        .zip(&plays)
+        .map(|(&opp, &me)| round_score(game, opp, me))
+        .sum();
+    return Some(Plan { plays, score });
+}
+
+fn best_per_round(game: &Game, opponents: &[Play]) -> Vec<Play> {
+    return opponents
+        .iter()
+        .map(|&opp| {
+            return game
+                .plays()
+                .max_by_key(|&me| round_score(game, opp, me))
+                .unwrap();
+        })
+        .collect();
+}
+
+/// Keeps, per number of losses so far, the best score and the play that got there.
+fn with_max_losses(game: &Game, opponents: &[Play], max_losses: usize) -> Option<Vec<Play>> {
+    let max_losses = max_losses.min(opponents.len());
+    let mut best: Vec<Option<i32>> = vec![None; max_losses + 1];
+    best[0] = Some(0);
+    // choices[round][losses] = (losses before the round, play)
+    let mut choices: Vec<Vec<Option<(usize, Play)>>> = vec![];
+
+    for &opp in opponents {
+        let mut next: Vec<Option<i32>> = vec![None; max_losses + 1];
+        let mut choice = vec![None; max_losses + 1];
+
+        for (losses, score) in best.iter().enumerate() {
+            let Some(score) = score else { continue };
+            for me in game.plays() {
+                let lost = (game.evaluate(opp, me) == Outcome::Lose) as usize;
+                let total = score + round_score(game, opp, me);
+                if losses + lost <= max_losses && next[losses + lost] < Some(total) {
+                    next[losses + lost] = Some(total);
+                    choice[losses + lost] = Some((losses, me));
+                }
+            }
+        }
+        best = next;
+        choices.push(choice);
+    }
+
+    let (mut losses, _) = best
+        .iter()
+        .enumerate()
+        .filter_map(|(losses, score)| Some((losses, (*score)?)))
+        .max_by_key(|&(losses, score)| (score, std::cmp::Reverse(losses)))?;
+
+    let mut plays = vec![];
+    for choice in choices.iter().rev() {
+        let (before, me) = choice[losses].unwrap();
+        plays.push(me);
+        losses = before;
+    }
+    plays.reverse();
+    return Some(plays);
+}
+
+/// Rounds only differ by the opponent's play, so this is a transportation problem
+/// from opponent plays (supply: how often each was played) to our plays (capacity:
+/// `max_uses`), solved as a min-cost flow on a graph of `2m + 2` nodes.
+fn with_max_uses(game: &Game, opponents: &[Play], max_uses: usize) -> Option<Vec<Play>> {
+    let m = game.plays().count();
+    let mut counts = vec![0; m];
+    for opp in opponents {
+        counts[opp.0] += 1;
+    }
+
+    let (source, sink) = (0, 2 * m + 1);
+    let mut flow = FlowGraph::new(2 * m + 2);
+    for opp in game.plays() {
+        flow.add_edge(source, 1 + opp.0, counts[opp.0], 0);
+        for me in game.plays() {
+            flow.add_edge(
+                1 + opp.0,
+                1 + m + me.0,
+                counts[opp.0],
+                -round_score(game, opp, me),
+            );
+        }
+    }
+    for me in game.plays() {
+        flow.add_edge(1 + m + me.0, sink, max_uses, 0);
+    }
+
+    if flow.min_cost_flow(source, sink) < opponents.len() {
+        return None;
+    }
+
+    // assigned[opp][me] = rounds against `opp` answered with `me`
+    let mut assigned: Vec<Vec<usize>> = (0..m)
+        .map(|opp| (0..m).map(|me| flow.flow(1 + opp, 1 + m + me)).collect())
+        .collect();
+
+    return Some(
+        opponents
+            .iter()
+            .map(|opp| {
+                let me = assigned[opp.0].iter().position(|&n| n > 0).unwrap();
+                assigned[opp.0][me] -= 1;
+                return Play(me);
+            })
+            .collect(),
+    );
+}
+
+struct Edge {
+    to: usize,
+    cap: usize,
+    cost: i32,
+}
+
+/// Residual graph; each edge is stored next to its reverse (`idx ^ 1`).
+struct FlowGraph {
+    edges: Vec<Edge>,
+    adjacent: Vec<Vec<usize>>,
+}
+
+impl FlowGraph {
+    fn new(nodes: usize) -> Self {
+        return Self {
+            edges: vec![],
+            adjacent: vec![vec![]; nodes],
+        };
+    }
+
+    fn add_edge(&mut self, from: usize, to: usize, cap: usize, cost: i32) {
+        self.adjacent[from].push(self.edges.len());
+        self.edges.push(Edge { to, cap, cost });
+        self.adjacent[to].push(self.edges.len());
+        self.edges.push(Edge {
+            to: from,
+            cap: 0,
+            cost: -cost,
+        });
+    }
+
+    /// Flow pushed along the forward edge `from -> to`.
+    fn flow(&self, from: usize, to: usize) -> usize {
+        return self.adjacent[from]
+            .iter()
+            .filter(|&&idx| idx % 2 == 0 && self.edges[idx].to == to)
+            .map(|&idx| self.edges[idx ^ 1].cap)
+            .sum();
+    }
+
+    /// Successive shortest paths with Bellman-Ford, which copes with the negative
+    /// costs. Returns the total flow pushed.
+    fn min_cost_flow(&mut self, source: usize, sink: usize) -> usize {
+        let mut total = 0;
+
+        loop {
+            let mut dist: Vec<Option<i32>> = vec![None; self.adjacent.len()];
+            let mut via: Vec<Option<usize>> = vec![None; self.adjacent.len()];
+            dist[source] = Some(0);
+
+            for _ in 0..self.adjacent.len() {
+                let mut changed = false;
+                for (idx, edge) in self.edges.iter().enumerate() {
+                    let from = self.edges[idx ^ 1].to;
+                    let Some(d) = dist[from] else { continue };
+                    if edge.cap > 0 && dist[edge.to].is_none_or(|old| d + edge.cost < old) {
+                        dist[edge.to] = Some(d + edge.cost);
+                        via[edge.to] = Some(idx);
+                        changed = true;
+                    }
+                }
+                if !changed {
+                    break;
+                }
+            }
+
+            if dist[sink].is_none() {
+                return total;
+            }
+
+            let mut path = vec![];
+            let mut node = sink;
+            while let Some(idx) = via[node] {
+                path.push(idx);
+                node = self.edges[idx ^ 1].to;
+            }
+            let pushed = path.iter().map(|&idx| self.edges[idx].cap).min().unwrap();
+            for idx in path {
+                self.edges[idx].cap -= pushed;
+                self.edges[idx ^ 1].cap += pushed;
+            }
+            total += pushed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::ROCK_PAPER_SCISSORS;
+    use crate::simulate::Rng;
+
+    /// Tries every sequence of plays.
+    fn brute_force(game: &Game, opponents: &[Play], constraint: Constraint) -> Option<i32> {
+        let m = game.plays().count();
+        let mut best = None;
+        for code in 0..m.pow(opponents.len() as u32) {
+            let plays: Vec<Play> = (0..opponents.len())
+                .map(|idx| Play(code / m.pow(idx as u32) % m))
+                .collect();
+            let allowed = match constraint {
+                Constraint::Unlimited => true,
+                Constraint::MaxUses(uses) => game
+                    .plays()
+                    .all(|p| plays.iter().filter(|&&me| me == p).count() <= uses),
+                Constraint::MaxLosses(losses) => {
+                    opponents
+                        .iter()
+                        .zip(&plays)
+                        .filter(|&(&opp, &me)| game.evaluate(opp, me) == Outcome::Lose)
+                        .count()
+                        <= losses
+                }
+            };
+            if allowed {
+                let score = opponents
+                    .iter()
+                    .zip(&plays)
+                    .map(|(&opp, &me)| round_score(game, opp, me))
+                    .sum();
+                best = best.max(Some(score));
+            }
+        }
+        return best;
+    }
+
+    #[test]
+    fn counters_the_example() {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let [rock, paper, scissors] = [Play(0), Play(1), Play(2)];
+
+        let plan = optimise(&game, &[rock, paper, scissors], Constraint::Unlimited).unwrap();
+        assert_eq!(plan.plays, vec![paper, scissors, rock]);
+        assert_eq!(plan.score, 24);
+
+        // paper once wins, then a draw beats a loss
+        let plan = optimise(&game, &[rock, rock, rock], Constraint::MaxUses(1)).unwrap();
+        assert_eq!(plan.score, 8 + 4 + 3);
+        assert_eq!(
+            optimise(&game, &[rock, rock, rock], Constraint::MaxUses(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let games = [
+            Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap(),
+            Game::cyclic(&["a", "b", "c", "d", "e", "f", "g", "h", "i"]).unwrap(),
+        ];
+        let mut rng = Rng::new(2);
+
+        for game in &games {
+            let m = game.plays().count();
+            for _ in 0..10 {
+                let opponents: Vec<Play> = (0..5).map(|_| Play(rng.below(m))).collect();
+                for constraint in [
+                    Constraint::Unlimited,
+                    Constraint::MaxUses(1),
+                    Constraint::MaxUses(2),
+                    Constraint::MaxLosses(0),
+                    Constraint::MaxLosses(2),
+                ] {
+                    let plan = optimise(game, &opponents, constraint);
+                    assert_eq!(
+                        plan.as_ref().map(|plan| plan.score),
+                        brute_force(game, &opponents, constraint),
+                        "{:?} against {:?}",
+                        constraint,
+                        opponents
+                    );
+                }
+            }
+        }
+    }
+}
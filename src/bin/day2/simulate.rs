@@ -0,0 +1,222 @@
+use crate::game::{Game, Play};
+use crate::Outcome;
+
+/// Small seeded PRNG (SplitMix64), so simulations replay exactly from a seed.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        return Self(seed);
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        return z ^ (z >> 31);
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        return (self.next_u64() % n as u64) as usize;
+    }
+}
+
+/// One round as seen by a player: `(own play, other player's play)`.
+pub type Round = (Play, Play);
+
+pub trait Strategy {
+    fn name(&self) -> String;
+    fn next(&mut self, game: &Game, history: &[Round], rng: &mut Rng) -> Play;
+}
+
+/// Plays a strategy guide column in order, starting over when it runs out.
+pub struct Guide(pub Vec<Play>);
+
+/// Picks uniformly at random.
+pub struct Random;
+
+/// Counters the other player's most frequent play so far.
+pub struct FrequencyCounter;
+
+/// Counters the other player's previous play.
+pub struct BeatLast;
+
+impl Strategy for Guide {
+    fn name(&self) -> String {
+        return "guide".to_string();
+    }
+
+    fn next(&mut self, game: &Game, history: &[Round], rng: &mut Rng) -> Play {
+        if self.0.is_empty() {
+            return Random.next(game, history, rng);
+        }
+        return self.0[history.len() % self.0.len()];
+    }
+}
+
+impl Strategy for Random {
+    fn name(&self) -> String {
+        return "random".to_string();
+    }
+
+    fn next(&mut self, game: &Game, _: &[Round], rng: &mut Rng) -> Play {
+        return Play(rng.below(game.plays().count()));
+    }
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self) -> String {
+        return "frequency".to_string();
+    }
+
+    fn next(&mut self, game: &Game, history: &[Round], rng: &mut Rng) -> Play {
+        let mut counts = vec![0; game.plays().count()];
+        for &(_, theirs) in history {
+            counts[theirs.0] += 1;
+        }
+        // first most frequent play wins ties
+        let most_frequent = counts
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|&(_, count)| count)
+            .map(|(idx, _)| Play(idx));
+
+        return match (history.is_empty(), most_frequent) {
+            (false, Some(theirs)) => counter(game, theirs),
+            _ => Random.next(game, history, rng),
+        };
+    }
+}
+
+impl Strategy for BeatLast {
+    fn name(&self) -> String {
+        return "beat-last".to_string();
+    }
+
+    fn next(&mut self, game: &Game, history: &[Round], rng: &mut Rng) -> Play {
+        return match history.last() {
+            Some(&(_, theirs)) => counter(game, theirs),
+            None => Random.next(game, history, rng),
+        };
+    }
+}
+
+fn counter(game: &Game, theirs: Play) -> Play {
+    return game.find_play(theirs, Outcome::Win).unwrap_or(theirs);
+}
+
+pub fn strategy(name: &str, guide: &[Play]) -> Option<Box<dyn Strategy>> {
+    return match name {
+        "guide" => Some(Box::new(Guide(guide.to_vec()))),
+        "random" => Some(Box::new(Random)),
+        "frequency" => Some(Box::new(FrequencyCounter)),
+        "beat-last" => Some(Box::new(BeatLast)),
+        _ => None,
+    };
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// Our total score of every match
+    pub scores: Vec<i32>,
+}
+
+impl Summary {
+    pub fn rounds(&self) -> usize {
+        return self.wins + self.draws + self.losses;
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        return self.wins as f64 / self.rounds().max(1) as f64;
+    }
+
+    /// Score at the given percentile (0-100) across matches.
+    pub fn percentile(&self, percentile: usize) -> Option<i32> {
+        let mut scores = self.scores.clone();
+        scores.sort();
+        let idx = (scores.len().saturating_sub(1) * percentile).div_ceil(100);
+        return scores.get(idx).copied();
+    }
+}
+
+/// Plays `matches` matches of `rounds` rounds, scoring every round like part one.
+///
+/// Match `n` draws its randomness from `seed + n`, so any single match can be
+/// replayed on its own. Strategies only see the history of the current match.
+pub fn simulate(
+    game: &Game,
+    me: &mut dyn Strategy,
+    opponent: &mut dyn Strategy,
+    rounds: usize,
+    matches: usize,
+    seed: u64,
+) -> Summary {
+    let mut summary = Summary::default();
+
+    for n in 0..matches {
+        let mut rng = Rng::new(seed.wrapping_add(n as u64));
+        let mut mine: Vec<Round> = vec![];
+        let mut theirs: Vec<Round> = vec![];
+        let mut score = 0;
+
+        for _ in 0..rounds {
+            let my_play = me.next(game, &mine, &mut rng);
+            let their_play = opponent.next(game, &theirs, &mut rng);
+            let outcome = game.evaluate(their_play, my_play);
+
+            match outcome {
+                Outcome::Win => summary.wins += 1,
+                Outcome::Draw => summary.draws += 1,
+                Outcome::Lose => summary.losses += 1,
+            }
+            score += outcome.score() + my_play.score();
+            mine.push((my_play, their_play));
+            theirs.push((their_play, my_play));
+        }
+        summary.scores.push(score);
+    }
+
+    return summary;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::game::ROCK_PAPER_SCISSORS;
+
+    #[test]
+    fn guides_replay_part_one() {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let mut me = Guide(vec![Play(1), Play(0), Play(2)]);
+        let mut opponent = Guide(vec![Play(0), Play(1), Play(2)]);
+
+        let summary = simulate(&game, &mut me, &mut opponent, 3, 2, 0);
+        assert_eq!(summary.scores, vec![15, 15]);
+        assert_eq!((summary.wins, summary.draws, summary.losses), (2, 2, 2));
+    }
+
+    #[test]
+    fn adaptive_strategies_exploit_fixed_ones() {
+        let game = Game::cyclic(&ROCK_PAPER_SCISSORS).unwrap();
+        let mut opponent = Guide(vec![Play(0)]);
+
+        let summary = simulate(&game, &mut BeatLast, &mut opponent, 100, 3, 7);
+        // only the first round of each match is a guess
+        assert!(summary.wins >= 3 * 99);
+        assert!(summary.win_rate() > 0.98);
+
+        let again = simulate(&game, &mut Random, &mut FrequencyCounter, 50, 4, 42);
+        assert_eq!(
+            again,
+            simulate(&game, &mut Random, &mut FrequencyCounter, 50, 4, 42)
+        );
+        assert_eq!(again.rounds(), 200);
+    }
+}
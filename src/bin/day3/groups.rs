@@ -0,0 +1,165 @@
+use crate::items::{ItemSet, UnknownItem};
+use crate::scheme::PriorityScheme;
+use std::fmt::{self, Display};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GroupError {
+    /// Groups of no rucksacks can't share a badge
+    Empty,
+    /// The input ran out before the last group was filled
+    Incomplete {
+        rucksacks: usize,
+        expected: usize,
+    },
+    NoBadge,
+    SeveralBadges(ItemSet),
+    Item(UnknownItem),
+}
+
+impl Display for GroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "groups need at least one rucksack"),
+            Self::Incomplete {
+                rucksacks,
+                expected,
+            } => write!(f, "only {} of {} rucksacks", rucksacks, expected),
+            Self::NoBadge => write!(f, "no item is in every rucksack"),
+            Self::SeveralBadges(common) => write!(
+                f,
+                "{} items are in every rucksack (priorities {:?})",
+                common.len(),
+                common.priorities().collect::<Vec<_>>()
+            ),
+            Self::Item(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GroupError {}
+
+impl From<UnknownItem> for GroupError {
+    fn from(err: UnknownItem) -> Self {
+        return Self::Item(err);
+    }
+}
+
+/// A group's error, with the group's position in the input (from 1).
+#[derive(Debug, PartialEq, Eq)]
+pub struct BadgeError {
+    pub group: usize,
+    pub error: GroupError,
+}
+
+impl Display for BadgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "group {}: {}", self.group, self.error)
+    }
+}
+
+impl std::error::Error for BadgeError {}
+
+/// The single item type carried by every rucksack of the group, as a one-item set.
+pub fn find_common(group: &[&str], scheme: &dyn PriorityScheme) -> Result<ItemSet, GroupError> {
+    let Some((first, rest)) = group.split_first() else {
+        return Err(GroupError::Empty);
+    };
+
+    let mut common = ItemSet::from_items(first, scheme)?;
+    for rucksack in rest {
+        common = common.intersection(ItemSet::from_items(rucksack, scheme)?);
+    }
+
+    match common.len() {
+        0 => return Err(GroupError::NoBadge),
+        1 => return Ok(common),
+        _ => return Err(GroupError::SeveralBadges(common)),
+    }
+}
+
+/// Splits the rucksacks into groups of `group_size` and finds each group's badge.
+pub fn find_badges(
+    rucksacks: &[&str],
+    group_size: usize,
+    scheme: &dyn PriorityScheme,
+) -> Result<Vec<ItemSet>, BadgeError> {
+    if group_size == 0 {
+        return Err(BadgeError {
+            group: 1,
+            error: GroupError::Empty,
+        });
+    }
+    return rucksacks
+        .chunks(group_size)
+        .enumerate()
+        .map(|(idx, group)| {
+            let error = |error| BadgeError {
+                group: idx + 1,
+                error,
+            };
+            if group.len() < group_size {
+                return Err(error(GroupError::Incomplete {
+                    rucksacks: group.len(),
+                    expected: group_size,
+                }));
+            }
+            return find_common(group, scheme).map_err(error);
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::scheme::Ascii;
+
+    #[test]
+    fn finds_one_badge_for_any_size() {
+        let badge = ItemSet::from_items("r", &Ascii).unwrap();
+        assert_eq!(find_common(&["abr", "rcd"], &Ascii), Ok(badge));
+        assert_eq!(
+            find_common(&["abr", "rcd", "Rrx", "yrY"], &Ascii),
+            Ok(badge)
+        );
+        assert_eq!(find_common(&["ab", "cd"], &Ascii), Err(GroupError::NoBadge));
+        assert_eq!(find_common(&[], &Ascii), Err(GroupError::Empty));
+        assert_eq!(
+            find_common(&["abr", "abd"], &Ascii),
+            Err(GroupError::SeveralBadges(
+                ItemSet::from_items("ab", &Ascii).unwrap()
+            ))
+        );
+        assert_eq!(
+            find_common(&["ab", "a1"], &Ascii),
+            Err(GroupError::Item(UnknownItem('1')))
+        );
+    }
+
+    #[test]
+    fn rejects_incomplete_groups() {
+        assert_eq!(
+            find_badges(&["ab", "ac", "ad", "bd"], 3, &Ascii),
+            Err(BadgeError {
+                group: 2,
+                error: GroupError::Incomplete {
+                    rucksacks: 1,
+                    expected: 3
+                }
+            })
+        );
+        assert_eq!(
+            find_badges(&["ab", "ac", "bd", "dc"], 2, &Ascii)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            find_badges(&["ab"], 0, &Ascii),
+            Err(BadgeError {
+                group: 1,
+                error: GroupError::Empty
+            })
+        );
+    }
+}
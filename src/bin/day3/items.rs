@@ -0,0 +1,85 @@
+use crate::scheme::PriorityScheme;
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownItem(pub char);
+
+impl Display for UnknownItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "item {:?} has no priority", self.0)
+    }
+}
+
+impl std::error::Error for UnknownItem {}
+
+/// Set of item types, stored as a bitmask with each item at the bit of its priority.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: Self = Self(0);
+
+    pub fn single(priority: u32) -> Self {
+        return Self(1 << priority);
+    }
+
+    pub fn from_items(items: &str, scheme: &dyn PriorityScheme) -> Result<Self, UnknownItem> {
+        return items.chars().try_fold(Self::EMPTY, |set, item| {
+            Ok(set.union(Self::single(scheme.priority(item)?)))
+        });
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        return Self(self.0 | other.0);
+    }
+
+    pub fn intersection(self, other: Self) -> Self {
+        return Self(self.0 & other.0);
+    }
+
+    pub fn len(self) -> u32 {
+        return self.0.count_ones();
+    }
+
+    pub fn is_empty(self) -> bool {
+        return self.0 == 0;
+    }
+
+    /// Lowest priority in the set.
+    pub fn first(self) -> Option<u32> {
+        if self.is_empty() {
+            return None;
+        }
+        return Some(self.0.trailing_zeros());
+    }
+
+    /// Priorities in the set, lowest first.
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        return (0..64).filter(move |&bit| self.0 & (1 << bit) != 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::scheme::Ascii;
+
+    #[test]
+    fn combines_sets() {
+        let first = ItemSet::from_items("vJrwpWtwJgWr", &Ascii).unwrap();
+        let second = ItemSet::from_items("hcsFMMfFFhFp", &Ascii).unwrap();
+
+        let shared = first.intersection(second);
+        assert_eq!(shared, ItemSet::single(16));
+        assert_eq!(shared.first(), Some(16));
+        assert_eq!(first.union(second).intersection(shared), shared);
+        assert_eq!(first.union(second).len(), first.len() + second.len() - 1);
+        assert_eq!(
+            first.priorities().collect::<Vec<_>>(),
+            vec![7, 16, 18, 20, 22, 23, 36, 49]
+        );
+        assert!(ItemSet::EMPTY.is_empty());
+        assert!(ItemSet::from_items("ab!", &Ascii).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use advent_of_code::config::{self, Validate};
+use advent_of_code::{cli::Args, trace};
+use serde::{Deserialize, Serialize};
+
+mod groups;
+mod items;
+mod rearrange;
+mod regroup;
+mod scheme;
+mod validate;
+
+use groups::BadgeError;
+use items::{ItemSet, UnknownItem};
+use scheme::{Ascii, PriorityScheme, Table};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    /// How many elves share a badge
+    group_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Self { group_size: 3 };
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.group_size == 0 {
+            return Err("group_size must be at least 1".to_string());
+        }
+        return Ok(());
+    }
+}
+
+fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day3");
+    let config: Config = config::load(&args, "day3").expect("Invalid config");
+    let file_data = std::fs::read_to_string("src/inputs/day3.txt").expect("Failed to read the file!");
+
+    let table;
+    let scheme: &dyn PriorityScheme = match args.value("--priorities") {
+        Some(path) => {
+            let items = std::fs::read_to_string(path).expect("Failed to read the priorities!");
+            table = Table::parse(&items).expect("Invalid priorities");
+            &table
+        }
+        None => &Ascii,
+    };
+
+    if args.flag("--validate") {
+        print_validation(&file_data, config.group_size, scheme);
+        return;
+    }
+
+    if args.flag("--rearrange") {
+        print_rearrangements(&file_data, scheme);
+        return;
+    }
+
+    if args.flag("--regroup") {
+        let budget = args.value("--budget").map_or(10_000, |budget| {
+            budget.parse().expect("--budget is not a number")
+        });
+        print_regrouping(&file_data, config.group_size, budget, scheme);
+        return;
+    }
+
+    let overlap = get_overlapping_score(&file_data, scheme).expect("Invalid rucksack");
+    let badges = get_badges_score(&file_data, config.group_size, scheme).expect("Invalid group");
+
+    println!("Item overlap priority sum is {:?}", &overlap);
+    println!("Badges priority sum is {:?}", &badges);
+}
+
+fn print_validation(file_data: &str, group_size: usize, scheme: &dyn PriorityScheme) {
+    let issues = validate::validate(file_data, group_size, scheme);
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    println!("{} issues", issues.len());
+}
+
+/// Prints the rucksacks whose compartments share items, fixed with the fewest swaps.
+fn print_rearrangements(file_data: &str, scheme: &dyn PriorityScheme) {
+    let mut total = 0;
+    for (idx, rucksack) in file_data.lines().enumerate() {
+        match rearrange::rearrange(rucksack, scheme) {
+            Ok(fixed) if fixed.swaps.is_empty() => {}
+            Ok(fixed) => {
+                println!(
+                    "line {}: {} swaps: {}",
+                    idx + 1,
+                    fixed.swaps.len(),
+                    fixed.rucksack
+                );
+                total += fixed.swaps.len();
+            }
+            Err(err) => println!("line {}: {}", idx + 1, err),
+        }
+    }
+    println!("{} swaps in total", total);
+}
+
+fn print_regrouping(
+    file_data: &str,
+    group_size: usize,
+    budget: usize,
+    scheme: &dyn PriorityScheme,
+) {
+    let rucksacks: Vec<ItemSet> = file_data
+        .lines()
+        .map(|rucksack| ItemSet::from_items(rucksack, scheme).expect("Invalid rucksack"))
+        .collect();
+
+    let regrouping = match regroup::regroup(&rucksacks, group_size, budget) {
+        Ok(regrouping) => regrouping,
+        Err(err) => {
+            println!("{}", err);
+            return;
+        }
+    };
+    for group in &regrouping.groups {
+        let lines: Vec<String> = group.iter().map(|idx| (idx + 1).to_string()).collect();
+        println!("lines {}", lines.join(", "));
+    }
+    if regrouping.optimal {
+        println!("Lowest badges priority sum is {}", regrouping.cost);
+    } else {
+        println!(
+            "Lowest badges priority sum found is {} (search budget ran out)",
+            regrouping.cost
+        );
+    }
+}
+
+/// Items of each compartment, the first half of the rucksack and the second.
+fn compartments(
+    rucksack: &str,
+    scheme: &dyn PriorityScheme,
+) -> Result<(ItemSet, ItemSet), UnknownItem> {
+    // halve by items rather than bytes, as a scheme's items needn't be ASCII
+    let middle = rucksack
+        .char_indices()
+        .nth(rucksack.chars().count() / 2)
+        .map_or(rucksack.len(), |(idx, _)| idx);
+    let (first, second) = rucksack.split_at(middle);
+    return Ok((
+        ItemSet::from_items(first, scheme)?,
+        ItemSet::from_items(second, scheme)?,
+    ));
+}
+
+#[tracing::instrument(skip_all)]
+fn get_badges_score(
+    input: &str,
+    group_size: usize,
+    scheme: &dyn PriorityScheme,
+) -> Result<u32, BadgeError> {
+    let rucksacks: Vec<&str> = input.lines().collect();
+    let badges = groups::find_badges(&rucksacks, group_size, scheme)?;
+    return Ok(badges.iter().filter_map(|badge| badge.first()).sum());
+}
+
+#[tracing::instrument(skip_all)]
+fn get_overlapping_score(input: &str, scheme: &dyn PriorityScheme) -> Result<u32, UnknownItem> {
+    let mut score = 0;
+
+    for rucksack in input.lines() {
+        let (first, second) = compartments(rucksack, scheme)?;
+        score += first.intersection(second).first().unwrap_or(0);
+    }
+
+    return Ok(score);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../test_inputs/day3.test.txt");
+
+    #[test]
+    fn solve_overlapping_success() {
+        assert_eq!(get_overlapping_score(TEST_INPUT, &Ascii), Ok(78));
+        let crlf = TEST_INPUT.replace('\n', "\r\n");
+        assert_eq!(get_overlapping_score(&crlf, &Ascii), Ok(78))
+    }
+
+    #[test]
+    fn solve_badges_success() {
+        assert_eq!(get_badges_score(TEST_INPUT, 3, &Ascii), Ok(20))
+    }
+
+    #[test]
+    fn scores_other_alphabets() {
+        let table = Table::parse("0123456789éàü").unwrap();
+        // compartments hold two items each, whatever their width in bytes
+        assert_eq!(
+            get_overlapping_score(
+                "é1ü1
+àéé0",
+                &table
+            ),
+            Ok(2 + 11)
+        );
+        assert_eq!(
+            get_badges_score(
+                "é1ü
+2é
+àé",
+                3,
+                &table
+            ),
+            Ok(11)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::items::UnknownItem;
+use crate::scheme::PriorityScheme;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RearrangeError {
+    /// The compartments can't be told apart
+    OddLength(usize),
+    /// No split of the item types fills exactly half the rucksack
+    Impossible,
+    Item(UnknownItem),
+}
+
+impl Display for RearrangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OddLength(len) => write!(f, "odd number of items ({})", len),
+            Self::Impossible => write!(f, "no way to keep every item type on one side"),
+            Self::Item(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for RearrangeError {}
+
+impl From<UnknownItem> for RearrangeError {
+    fn from(err: UnknownItem) -> Self {
+        return Self::Item(err);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rearrangement {
+    pub rucksack: String,
+    /// Positions swapped, in the first compartment and in the second (from 0)
+    pub swaps: Vec<(usize, usize)>,
+}
+
+/// The fewest swaps between the compartments that leave no item type in both,
+/// preferring to move low-priority items when there's a choice.
+///
+/// Each item type ends up wholly in one compartment, and the types kept in the
+/// first must fill exactly half the rucksack. Choosing them is a knapsack over the
+/// types: filling `n` slots costs the items that have to cross over. Every swap
+/// moves one item each way, so that is twice the number of swaps.
+pub fn rearrange(
+    rucksack: &str,
+    scheme: &dyn PriorityScheme,
+) -> Result<Rearrangement, RearrangeError> {
+    let items: Vec<char> = rucksack.chars().collect();
+    if items.len() % 2 == 1 {
+        return Err(RearrangeError::OddLength(items.len()));
+    }
+    let half = items.len() / 2;
+
+    // item -> (count in the first compartment, count in the second)
+    let mut counts: BTreeMap<char, (usize, usize)> = BTreeMap::new();
+    for (pos, &item) in items.iter().enumerate() {
+        scheme.priority(item)?;
+        let count = counts.entry(item).or_default();
+        if pos < half {
+            count.0 += 1;
+        } else {
+            count.1 += 1;
+        }
+    }
+    let types: Vec<(char, usize, usize)> = counts
+        .iter()
+        .map(|(&item, &(first, second))| (item, first, second))
+        .collect();
+
+    // best[n] = cheapest (items moved, their priority) keeping n items in the first
+    // compartment, over the types seen so far; keep[t][n] = whether type t stays
+    // in the first compartment in that solution
+    let mut best: Vec<Option<(usize, u32)>> = vec![None; half + 1];
+    best[0] = Some((0, 0));
+    let mut keep: Vec<Vec<bool>> = vec![];
+
+    for &(item, first, second) in &types {
+        let priority = scheme.priority(item)?;
+        let mut next: Vec<Option<(usize, u32)>> = vec![None; half + 1];
+        let mut kept = vec![false; half + 1];
+
+        for (n, cost) in best.iter().enumerate() {
+            let Some((moved, moved_priority)) = *cost else {
+                continue;
+            };
+            // to the second compartment, moving the items in the first
+            let to_second = (moved + first, moved_priority + first as u32 * priority);
+            if next[n].is_none_or(|old| to_second < old) {
+                next[n] = Some(to_second);
+                kept[n] = false;
+            }
+            // to the first compartment, moving the items in the second
+            let to_first = (moved + second, moved_priority + second as u32 * priority);
+            let n = n + first + second;
+            if n <= half && next[n].is_none_or(|old| to_first < old) {
+                next[n] = Some(to_first);
+                kept[n] = true;
+            }
+        }
+        best = next;
+        keep.push(kept);
+    }
+    if best[half].is_none() {
+        return Err(RearrangeError::Impossible);
+    }
+
+    let mut in_first = BTreeMap::new();
+    let mut n = half;
+    for (&(item, first, second), kept) in types.iter().zip(&keep).rev() {
+        in_first.insert(item, kept[n]);
+        if kept[n] {
+            n -= first + second;
+        }
+    }
+
+    let leaving_first = (0..half).filter(|&pos| !in_first[&items[pos]]);
+    let leaving_second = (half..items.len()).filter(|&pos| in_first[&items[pos]]);
+    let mut rearranged = items.clone();
+    let mut swaps = vec![];
+    for (a, b) in leaving_first.zip(leaving_second) {
+        rearranged.swap(a, b);
+        swaps.push((a, b - half));
+    }
+
+    return Ok(Rearrangement {
+        rucksack: rearranged.into_iter().collect(),
+        swaps,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::scheme::Ascii;
+
+    #[test]
+    fn swaps_the_cheapest_items() {
+        // moving p either way also needs one item of the other compartment to
+        // follow; c is the cheapest to bring over
+        let fixed = rearrange("vJrwpWtwJgWrhcsFMMfFFhFp", &Ascii).unwrap();
+        assert_eq!(fixed.rucksack, "vJrwcWtwJgWrhpsFMMfFFhFp");
+        assert_eq!(fixed.swaps, vec![(4, 1)]);
+
+        assert_eq!(rearrange("abab", &Ascii).unwrap().swaps.len(), 1);
+        assert_eq!(rearrange("abcd", &Ascii).unwrap().swaps, vec![]);
+    }
+
+    #[test]
+    fn reports_impossible_rucksacks() {
+        assert_eq!(rearrange("aaab", &Ascii), Err(RearrangeError::Impossible));
+        assert_eq!(rearrange("abc", &Ascii), Err(RearrangeError::OddLength(3)));
+        assert_eq!(
+            rearrange("a1a1", &Ascii),
+            Err(RearrangeError::Item(UnknownItem('1')))
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use crate::items::ItemSet;
+
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regrouping {
+    /// Indices of the rucksacks in each group
+    pub groups: Vec<Vec<usize>>,
+    /// Sum of the badges' priorities
+    pub cost: u32,
+    /// Whether the search finished, rather than running out of budget
+    pub optimal: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RegroupError {
+    /// Every possibility was ruled out
+    NoPartition,
+    /// The search gave up before finding any partition
+    OutOfBudget,
+}
+
+impl Display for RegroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoPartition => write!(f, "no grouping gives every group exactly one badge"),
+            Self::OutOfBudget => write!(f, "no grouping found within the search budget"),
+        }
+    }
+}
+
+impl std::error::Error for RegroupError {}
+
+/// Candidate group: its rucksacks (ascending) and the priority of its one badge.
+type Candidate = (Vec<usize>, u32);
+
+/// Partitions the rucksacks into groups of `group_size` sharing exactly one badge
+/// each, with the lowest total badge priority.
+///
+/// Depth-first search over the candidate groups, placing next the rucksack with
+/// the fewest groups left to join (and backing out as soon as one has none),
+/// cheapest groups first. A branch is cut once its cost plus, for every rucksack
+/// left, a share of the cheapest group it could ever join can't beat the best
+/// partition found so far. The search stops after visiting `budget` branches,
+/// keeping the best partition so far, as large inputs have far too many to prove.
+pub fn regroup(
+    rucksacks: &[ItemSet],
+    group_size: usize,
+    budget: usize,
+) -> Result<Regrouping, RegroupError> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return Err(RegroupError::NoPartition);
+    }
+
+    let mut groups = candidates(rucksacks, group_size);
+    groups.sort_by_key(|&(_, badge)| badge);
+
+    let mut containing: Vec<Vec<usize>> = vec![vec![]; rucksacks.len()];
+    for (id, (group, _)) in groups.iter().enumerate() {
+        for &idx in group {
+            containing[idx].push(id);
+        }
+    }
+    // cheapest badge each rucksack could carry, in any group
+    let cheapest: Vec<u32> = containing
+        .iter()
+        .map(|ids| ids.first().map(|&id| groups[id].1))
+        .collect::<Option<_>>()
+        .ok_or(RegroupError::NoPartition)?;
+
+    let mut search = Search {
+        group_size: group_size as u32,
+        live: containing.iter().map(|ids| ids.len()).collect(),
+        taken: vec![0; groups.len()],
+        grouped: vec![false; rucksacks.len()],
+        groups,
+        containing,
+        chosen: vec![],
+        best: None,
+        budget,
+    };
+    search.run(0, cheapest.iter().sum(), &cheapest);
+
+    let exhausted = search.budget == 0;
+    return match search.best {
+        Some(mut best) => {
+            best.optimal = !exhausted;
+            Ok(best)
+        }
+        None if exhausted => Err(RegroupError::OutOfBudget),
+        None => Err(RegroupError::NoPartition),
+    };
+}
+
+struct Search {
+    group_size: u32,
+    /// Every candidate group, cheapest first
+    groups: Vec<Candidate>,
+    /// Ids of the groups each rucksack belongs to, cheapest first
+    containing: Vec<Vec<usize>>,
+    /// How many rucksacks of each group are already grouped elsewhere
+    taken: Vec<usize>,
+    /// How many groups with nothing taken each rucksack belongs to
+    live: Vec<usize>,
+    grouped: Vec<bool>,
+    chosen: Vec<usize>,
+    best: Option<Regrouping>,
+    budget: usize,
+}
+
+impl Search {
+    /// `left` sums the cheapest badges of the rucksacks not grouped yet.
+    fn run(&mut self, cost: u32, left: u32, cheapest: &[u32]) {
+        let next = (0..self.grouped.len())
+            .filter(|&idx| !self.grouped[idx])
+            .min_by_key(|&idx| self.live[idx]);
+        let Some(next) = next else {
+            if self.best.as_ref().is_none_or(|best| cost < best.cost) {
+                self.best = Some(Regrouping {
+                    groups: self
+                        .chosen
+                        .iter()
+                        .map(|&id| self.groups[id].0.clone())
+                        .collect(),
+                    cost,
+                    optimal: false,
+                });
+            }
+            return;
+        };
+
+        if !self.may_beat_best(cost, left) || self.live[next] == 0 || self.budget == 0 {
+            return;
+        }
+        self.budget -= 1;
+
+        let options: Vec<usize> = self.containing[next]
+            .iter()
+            .copied()
+            .filter(|&id| self.taken[id] == 0)
+            .collect();
+        for id in options {
+            let freed: u32 = self.groups[id].0.iter().map(|&idx| cheapest[idx]).sum();
+            let (cost, left) = (cost + self.groups[id].1, left - freed);
+            if self.budget == 0 {
+                break;
+            }
+            if !self.may_beat_best(cost, left) {
+                continue;
+            }
+            self.place(id, true);
+            self.chosen.push(id);
+            self.run(cost, left, cheapest);
+            self.chosen.pop();
+            self.place(id, false);
+        }
+    }
+
+    /// Whether a partition costing `cost` so far, with `left` still to place, could
+    /// still beat the best one found.
+    fn may_beat_best(&self, cost: u32, left: u32) -> bool {
+        // compare in units of 1/group_size to keep the bound in integers
+        return self
+            .best
+            .as_ref()
+            .is_none_or(|best| cost * self.group_size + left < best.cost * self.group_size);
+    }
+
+    /// Groups (or ungroups) the rucksacks of group `id`, updating which other
+    /// groups remain possible.
+    fn place(&mut self, id: usize, grouping: bool) {
+        for pos in 0..self.groups[id].0.len() {
+            let idx = self.groups[id].0[pos];
+            self.grouped[idx] = grouping;
+            for &other in &self.containing[idx] {
+                if grouping {
+                    self.taken[other] += 1;
+                } else {
+                    self.taken[other] -= 1;
+                }
+                // the group just became impossible, or possible again
+                if self.taken[other] == grouping as usize {
+                    for &member in &self.groups[other].0 {
+                        if grouping {
+                            self.live[member] -= 1;
+                        } else {
+                            self.live[member] += 1;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Every group of `size` rucksacks sharing exactly one item, skipping partial
+/// groups as soon as they share nothing.
+fn candidates(rucksacks: &[ItemSet], size: usize) -> Vec<Candidate> {
+    fn extend(
+        rucksacks: &[ItemSet],
+        pool: &[usize],
+        size: usize,
+        group: &mut Vec<usize>,
+        common: ItemSet,
+        found: &mut Vec<Candidate>,
+    ) {
+        if group.len() == size {
+            if common.len() == 1 {
+                found.push((group.clone(), common.first().unwrap()));
+            }
+            return;
+        }
+        for (pos, &idx) in pool.iter().enumerate() {
+            let shared = common.intersection(rucksacks[idx]);
+            if shared.is_empty() {
+                continue;
+            }
+            group.push(idx);
+            extend(rucksacks, &pool[pos + 1..], size, group, shared, found);
+            group.pop();
+        }
+    }
+
+    let pool: Vec<usize> = (0..rucksacks.len()).collect();
+    let mut found = vec![];
+    for (pos, &idx) in pool.iter().enumerate() {
+        extend(
+            rucksacks,
+            &pool[pos + 1..],
+            size,
+            &mut vec![idx],
+            rucksacks[idx],
+            &mut found,
+        );
+    }
+    return found;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::scheme::Ascii;
+
+    fn sets(rucksacks: &[&str]) -> Vec<ItemSet> {
+        return rucksacks
+            .iter()
+            .map(|rucksack| ItemSet::from_items(rucksack, &Ascii).unwrap())
+            .collect();
+    }
+
+    /// Cost of the best partition, trying them all.
+    fn brute_force(rucksacks: &[ItemSet], left: Vec<usize>) -> Option<u32> {
+        let Some((&first, rest)) = left.split_first() else {
+            return Some(0);
+        };
+        let mut best = None;
+        for j in 0..rest.len() {
+            for k in j + 1..rest.len() {
+                let common = rucksacks[first]
+                    .intersection(rucksacks[rest[j]])
+                    .intersection(rucksacks[rest[k]]);
+                if common.len() != 1 {
+                    continue;
+                }
+                let others: Vec<usize> = rest
+                    .iter()
+                    .enumerate()
+                    .filter(|&(pos, _)| pos != j && pos != k)
+                    .map(|(_, &idx)| idx)
+                    .collect();
+                if let Some(cost) = brute_force(rucksacks, others) {
+                    let cost = cost + common.first().unwrap();
+                    best = Some(best.map_or(cost, |best: u32| best.min(cost)));
+                }
+            }
+        }
+        return best;
+    }
+
+    #[test]
+    fn regroups_for_cheaper_badges() {
+        // in file order the badges are Z and Y
+        let rucksacks = sets(&["aZ", "bZ", "bZ", "aY", "aY", "bY"]);
+        let regrouping = regroup(&rucksacks, 3, 1000).unwrap();
+        assert_eq!(regrouping.groups, vec![vec![0, 3, 4], vec![1, 2, 5]]);
+        assert_eq!(regrouping.cost, 1 + 2);
+        assert!(regrouping.optimal);
+
+        let none = Err(RegroupError::NoPartition);
+        assert_eq!(regroup(&sets(&["ab", "cd", "ef"]), 3, 1000), none);
+        assert_eq!(regroup(&sets(&["ab", "ab"]), 3, 1000), none);
+        // only groups with the one `ac` share a single item
+        assert_eq!(
+            regroup(&sets(&["ab", "ab", "ab", "ab", "ab", "ac"]), 3, 1000),
+            none
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut seed: u64 = 11;
+        let mut next = |n: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            return (seed >> 33) % n;
+        };
+
+        for _ in 0..200 {
+            let rucksacks: Vec<ItemSet> = (0..9)
+                .map(|_| {
+                    let items: String = (0..3).map(|_| (b'a' + next(6) as u8) as char).collect();
+                    return ItemSet::from_items(&items, &Ascii).unwrap();
+                })
+                .collect();
+            let expected = brute_force(&rucksacks, (0..9).collect());
+            assert_eq!(
+                regroup(&rucksacks, 3, usize::MAX).ok().map(|r| r.cost),
+                expected
+            );
+        }
+    }
+}
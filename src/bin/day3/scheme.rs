@@ -0,0 +1,106 @@
+use crate::items::UnknownItem;
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+
+/// Highest priority an `ItemSet` can hold.
+pub const MAX_PRIORITY: u32 = 63;
+
+/// How much each item type is worth, from 1 to `MAX_PRIORITY`.
+pub trait PriorityScheme {
+    fn priority(&self, item: char) -> Result<u32, UnknownItem>;
+}
+
+/// The puzzle's scheme: `a` to `z` are worth 1 to 26, `A` to `Z` 27 to 52.
+pub struct Ascii;
+
+impl PriorityScheme for Ascii {
+    fn priority(&self, item: char) -> Result<u32, UnknownItem> {
+        match item {
+            'a'..='z' => return Ok(item as u32 - 'a' as u32 + 1),
+            'A'..='Z' => return Ok(item as u32 - 'A' as u32 + 27),
+            _ => return Err(UnknownItem(item)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SchemeError {
+    Duplicate(char),
+    TooMany(usize),
+}
+
+impl Display for SchemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate(item) => write!(f, "{:?} is listed twice", item),
+            Self::TooMany(count) => {
+                write!(f, "{} items listed, at most {} fit", count, MAX_PRIORITY)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemeError {}
+
+/// Any alphabet, listed from lowest to highest priority.
+#[derive(Debug)]
+pub struct Table(HashMap<char, u32>);
+
+impl Table {
+    /// Reads the items in order, ignoring whitespace, so `"0123456789"` ranks the
+    /// digits 1 to 10. Files can spread the list over several lines.
+    pub fn parse(items: &str) -> Result<Self, SchemeError> {
+        let items: Vec<char> = items.chars().filter(|c| !c.is_whitespace()).collect();
+        if items.len() > MAX_PRIORITY as usize {
+            return Err(SchemeError::TooMany(items.len()));
+        }
+
+        let mut table = HashMap::new();
+        for (idx, &item) in items.iter().enumerate() {
+            if table.insert(item, idx as u32 + 1).is_some() {
+                return Err(SchemeError::Duplicate(item));
+            }
+        }
+        return Ok(Self(table));
+    }
+}
+
+impl PriorityScheme for Table {
+    fn priority(&self, item: char) -> Result<u32, UnknownItem> {
+        return self.0.get(&item).copied().ok_or(UnknownItem(item));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn ranks_letters() {
+        assert_eq!(Ascii.priority('a'), Ok(1));
+        assert_eq!(Ascii.priority('z'), Ok(26));
+        assert_eq!(Ascii.priority('A'), Ok(27));
+        assert_eq!(Ascii.priority('Z'), Ok(52));
+        assert_eq!(Ascii.priority('1'), Err(UnknownItem('1')));
+    }
+
+    #[test]
+    fn reads_custom_tables() {
+        let table = Table::parse("0123456789\néàü\n").unwrap();
+        assert_eq!(table.priority('0'), Ok(1));
+        assert_eq!(table.priority('é'), Ok(11));
+        assert_eq!(table.priority('ü'), Ok(13));
+        assert_eq!(table.priority('a'), Err(UnknownItem('a')));
+
+        assert_eq!(
+            Table::parse("aba").unwrap_err(),
+            SchemeError::Duplicate('a')
+        );
+        let too_many: String = ('\u{100}'..'\u{140}').collect();
+        assert_eq!(
+            Table::parse(&too_many).unwrap_err(),
+            SchemeError::TooMany(64)
+        );
+    }
+}
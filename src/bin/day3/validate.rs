@@ -0,0 +1,169 @@
+use crate::groups::{self, GroupError};
+use crate::items::ItemSet;
+use crate::scheme::PriorityScheme;
+use std::fmt::{self, Display};
+
+/// Something the puzzle's assumptions don't cover. Lines and groups count from 1.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// Halving the rucksack leaves one item in neither compartment
+    OddLength {
+        line: usize,
+        len: usize,
+    },
+    NoSharedItem {
+        line: usize,
+    },
+    SharedItems {
+        line: usize,
+        shared: ItemSet,
+    },
+    InvalidItem {
+        line: usize,
+        column: usize,
+        item: char,
+    },
+    /// No badge, several candidates, or too few rucksacks
+    Group {
+        group: usize,
+        error: GroupError,
+    },
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OddLength { line, len } => {
+                write!(f, "line {}: odd number of items ({})", line, len)
+            }
+            Self::NoSharedItem { line } => {
+                write!(f, "line {}: compartments share no item", line)
+            }
+            Self::SharedItems { line, shared } => write!(
+                f,
+                "line {}: compartments share {} items (priorities {:?})",
+                line,
+                shared.len(),
+                shared.priorities().collect::<Vec<_>>()
+            ),
+            Self::InvalidItem { line, column, item } => {
+                write!(
+                    f,
+                    "line {}, column {}: {:?} has no priority",
+                    line, column, item
+                )
+            }
+            Self::Group { group, error } => write!(f, "group {}: {}", group, error),
+        }
+    }
+}
+
+/// Checks every rucksack and group, rather than stopping at the first problem.
+/// Rucksacks with items the scheme can't rank are only reported for those items.
+pub fn validate(input: &str, group_size: usize, scheme: &dyn PriorityScheme) -> Vec<Issue> {
+    let rucksacks: Vec<&str> = input.lines().collect();
+    let mut issues = vec![];
+
+    for (idx, rucksack) in rucksacks.iter().enumerate() {
+        let line = idx + 1;
+        let invalid: Vec<Issue> = rucksack
+            .chars()
+            .enumerate()
+            .filter(|&(_, item)| scheme.priority(item).is_err())
+            .map(|(column, item)| Issue::InvalidItem {
+                line,
+                column: column + 1,
+                item,
+            })
+            .collect();
+        if !invalid.is_empty() {
+            issues.extend(invalid);
+            continue;
+        }
+
+        let len = rucksack.chars().count();
+        if len % 2 == 1 {
+            issues.push(Issue::OddLength { line, len });
+            continue;
+        }
+
+        let (first, second) = crate::compartments(rucksack, scheme).unwrap();
+        let shared = first.intersection(second);
+        match shared.len() {
+            0 => issues.push(Issue::NoSharedItem { line }),
+            1 => {}
+            _ => issues.push(Issue::SharedItems { line, shared }),
+        }
+    }
+
+    if group_size == 0 {
+        issues.push(Issue::Group {
+            group: 1,
+            error: GroupError::Empty,
+        });
+        return issues;
+    }
+
+    for (idx, group) in rucksacks.chunks(group_size).enumerate() {
+        let error = if group.len() < group_size {
+            Some(GroupError::Incomplete {
+                rucksacks: group.len(),
+                expected: group_size,
+            })
+        } else {
+            groups::find_common(group, scheme).err()
+        };
+
+        match error {
+            None | Some(GroupError::Item(_)) => {}
+            Some(error) => issues.push(Issue::Group {
+                group: idx + 1,
+                error,
+            }),
+        }
+    }
+
+    return issues;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::scheme::Ascii;
+
+    const TEST_INPUT: &str = include_str!("../../test_inputs/day3.test.txt");
+
+    #[test]
+    fn accepts_the_example() {
+        assert_eq!(validate(TEST_INPUT, 3, &Ascii), vec![]);
+        assert_eq!(
+            validate(TEST_INPUT, 0, &Ascii),
+            vec![Issue::Group {
+                group: 1,
+                error: GroupError::Empty
+            }]
+        );
+    }
+
+    #[test]
+    fn flags_every_problem() {
+        let issues = validate("abcab\nabab\nxy\nab\nab1-", 2, &Ascii);
+        let report: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+
+        assert_eq!(
+            report,
+            vec![
+                "line 1: odd number of items (5)",
+                "line 2: compartments share 2 items (priorities [1, 2])",
+                "line 3: compartments share no item",
+                "line 4: compartments share no item",
+                "line 5, column 3: '1' has no priority",
+                "line 5, column 4: '-' has no priority",
+                "group 1: 2 items are in every rucksack (priorities [1, 2])",
+                "group 2: no item is in every rucksack",
+                "group 3: only 1 of 2 rucksacks",
+            ]
+        );
+    }
+}
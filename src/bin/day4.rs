@@ -1,104 +1,213 @@
-use std::cmp::Ordering::{Equal, Greater, Less};
+use advent_of_code::interval::{Interval, IntervalSet};
+use advent_of_code::{cli::Args, trace};
+use std::fmt::{self, Display};
 
-type Assignment = (u8, u8);
+type Assignment = Interval<u8>;
 
-struct Pair {
-    first_assignment: Assignment,
-    second_assignment: Assignment,
+#[derive(Debug, PartialEq, Eq)]
+struct ParseError {
+    line: usize,
+    reason: String,
 }
 
-impl Pair {
-    fn new(line: &str) -> Option<Self> {
-        if let Some((first, second)) = line.split_once(",") {
-            return Some(Pair {
-                first_assignment: Self::parse_assignment(first),
-                second_assignment: Self::parse_assignment(second),
-            });
-        } else {
-            return None;
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The elves of one line, any number of them.
+#[derive(Debug)]
+struct Group {
+    assignments: Vec<Assignment>,
+}
+
+impl Group {
+    /// Parses line `line` (from 1) of the input, which must list at least two elves.
+    fn new(line: usize, data: &str) -> Result<Self, ParseError> {
+        let error = |reason: String| ParseError { line, reason };
+        let assignments = data
+            .split(",")
+            .map(Self::parse_assignment)
+            .collect::<Result<Vec<Assignment>, String>>()
+            .map_err(error)?;
+        if assignments.len() < 2 {
+            return Err(error("a group needs at least two elves".to_string()));
         }
+        return Ok(Group { assignments });
+    }
+
+    fn parse_assignment(assign_data: &str) -> Result<Assignment, String> {
+        let (start_data, end_data) = assign_data
+            .trim()
+            .split_once("-")
+            .ok_or_else(|| format!("no - in {:?}", assign_data))?;
+        let section = |data: &str| {
+            return data
+                .parse::<u8>()
+                .map_err(|_| format!("{:?} is not a section number", data));
+        };
+        return Interval::new(section(start_data)?, section(end_data)?)
+            .map_err(|err| err.to_string());
+    }
+
+    /// The first elf whose range contains everyone else's.
+    fn container(&self) -> Option<usize> {
+        return self.assignments.iter().position(|outer| {
+            return self.assignments.iter().all(|inner| outer.contains(inner));
+        });
+    }
+
+    /// `matrix[i][j]` is whether elves `i` and `j` share a section.
+    fn overlap_matrix(&self) -> Vec<Vec<bool>> {
+        return self
+            .assignments
+            .iter()
+            .map(|a| self.assignments.iter().map(|b| a.overlaps(b)).collect())
+            .collect();
     }
 
-    fn parse_assignment(assign_data: &str) -> Assignment {
-        let (start_data, end_data) = assign_data.split_once("-").expect("no - found");
-        let start = start_data.parse::<u8>().expect("not a u8");
-        let end = end_data.parse::<u8>().expect("not a u8");
+    /// Whether any two different elves share a section.
+    fn any_overlap(&self) -> bool {
+        let matrix = self.overlap_matrix();
+        return (0..matrix.len()).any(|i| (i + 1..matrix.len()).any(|j| matrix[i][j]));
+    }
 
-        return (start, end);
+    /// Sections every elf of the group is assigned.
+    fn common(&self) -> Option<Assignment> {
+        let (first, rest) = self.assignments.split_first()?;
+        return rest
+            .iter()
+            .try_fold(*first, |common, assignment| common.intersection(assignment));
     }
 }
 
 fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day4");
     let file_data =
         std::fs::read_to_string("src/inputs/day4.txt").expect("Failed to read the file!");
 
-    let pair_overlapping_count = calc_full_overlap(&file_data);
-    let all_overlapping_count = calc_all_overlap(&file_data);
+    if args.flag("--groups") {
+        print_groups(&file_data);
+        return;
+    }
+
+    if args.flag("--coverage") {
+        print_coverage(&file_data);
+        return;
+    }
+
+    let pair_overlapping_count = calc_full_overlap(&file_data).expect("Invalid assignments");
+    let all_overlapping_count = calc_all_overlap(&file_data).expect("Invalid assignments");
     println!("{:?}", pair_overlapping_count);
     println!("{:?}", all_overlapping_count);
 }
 
-fn parse_input(file_data: &str) -> Vec<Pair> {
-    return file_data
-        .lines()
-        .filter_map(|line| return Pair::new(line))
-        .collect::<Vec<Pair>>();
-}
+fn print_groups(file_data: &str) {
+    let groups = parse_input(file_data).expect("Invalid assignments");
+    for (idx, group) in groups.iter().enumerate() {
+        let assignments: Vec<String> = group.assignments.iter().map(|a| a.to_string()).collect();
+        let overlapping: Vec<String> = group
+            .overlap_matrix()
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                return (i + 1..row.len())
+                    .filter(move |&j| row[j])
+                    .map(move |j| format!("{}&{}", i + 1, j + 1));
+            })
+            .collect();
 
-fn calc_full_overlap(input: &str) -> i32 {
-    let assignments = parse_input(input);
-    return assignments
-        .iter()
-        .filter_map(|pair| {
-            let (first_start, first_end) = pair.first_assignment;
-            let (second_start, second_end) = pair.second_assignment;
-
-            // if the first pair range is smaller than the second pair range
-            if first_end - first_start <= second_end - second_start {
-                if is_fully_contained(first_start, first_end, second_start, second_end) {
-                    return Some(pair);
-                } else {
-                    return None;
-                };
+        println!(
+            "group {} ({}): contains all {}, common {}, overlapping {}",
+            idx + 1,
+            assignments.join(","),
+            group
+                .container()
+                .map_or("-".to_string(), |elf| format!("elf {}", elf + 1)),
+            group
+                .common()
+                .map_or("-".to_string(), |common| common.to_string()),
+            if overlapping.is_empty() {
+                "-".to_string()
             } else {
-                if is_fully_contained(second_start, second_end, first_start, first_end) {
-                    return Some(pair);
-                } else {
-                    return None;
-                };
+                overlapping.join(" ")
             }
-        })
-        .count() as i32;
+        );
+    }
+}
+
+/// Every elf's assignment in input order, with the group and elf (from 1) it's for.
+fn all_assignments(groups: &[Group]) -> (IntervalSet<u8>, Vec<(usize, usize)>) {
+    let elves = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(group, g)| (0..g.assignments.len()).map(move |elf| (group + 1, elf + 1)))
+        .collect();
+    let assignments = groups
+        .iter()
+        .flat_map(|group| group.assignments.iter().copied())
+        .collect();
+    return (assignments, elves);
 }
 
-fn is_fully_contained(
-    smaller_start: u8,
-    smaller_end: u8,
-    larger_start: u8,
-    larger_end: u8,
-) -> bool {
-    match (smaller_start >= larger_start, smaller_end <= larger_end) {
-        (true, true) => return true,
-        _ => return false,
+fn print_coverage(file_data: &str) {
+    let groups = parse_input(file_data).expect("Invalid assignments");
+    let (assignments, elves) = all_assignments(&groups);
+    let list = |intervals: Vec<Assignment>| {
+        let intervals: Vec<String> = intervals.iter().map(|i| i.to_string()).collect();
+        return intervals.join(", ");
+    };
+
+    println!("Covered: {}", list(assignments.coverage()));
+    println!("Covered by nobody: {}", list(assignments.gaps()));
+    println!(
+        "Covered by several elves: {}",
+        list(assignments.covered_by(2))
+    );
+
+    let redundant = assignments.redundant();
+    for &idx in &redundant {
+        let (group, elf) = elves[idx];
+        println!(
+            "Group {} elf {} ({}) is covered by the others",
+            group,
+            elf,
+            assignments.intervals()[idx]
+        );
     }
+    println!("{} redundant elves", redundant.len());
 }
 
-fn calc_all_overlap(file_data: &str) -> i32 {
-    let assignments = parse_input(file_data);
+#[tracing::instrument(skip_all)]
+fn parse_input(file_data: &str) -> Result<Vec<Group>, ParseError> {
+    return file_data
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| return Group::new(idx + 1, line))
+        .collect::<Result<Vec<Group>, ParseError>>();
+}
 
-    return assignments
+/// Groups where one elf's range contains all the others'.
+#[tracing::instrument(skip_all)]
+fn calc_full_overlap(input: &str) -> Result<i32, ParseError> {
+    let groups = parse_input(input)?;
+    return Ok(groups
         .iter()
-        .filter_map(|pair| {
-            let (first_start, first_end) = pair.first_assignment;
-            let (second_start, second_end) = pair.second_assignment;
+        .filter(|group| group.container().is_some())
+        .count() as i32);
+}
 
-            if first_start <= second_end && first_end >= second_start {
-                return Some(pair);
-            } else {
-                return None;
-            }
-        })
-        .count() as i32;
+/// Groups where any two elves share a section.
+#[tracing::instrument(skip_all)]
+fn calc_all_overlap(file_data: &str) -> Result<i32, ParseError> {
+    let groups = parse_input(file_data)?;
+
+    return Ok(groups.iter().filter(|group| group.any_overlap()).count() as i32);
 }
 
 #[cfg(test)]
@@ -108,13 +217,68 @@ mod tests {
 
     const TEST_INPUT: &str = include_str!("../test_inputs/day4.test.txt");
 
+    #[test]
+    fn parsed_groups() {
+        insta::assert_debug_snapshot!(parse_input(TEST_INPUT).unwrap());
+    }
+
     #[test]
     fn check_full_overlap() {
-        assert_eq!(calc_full_overlap(TEST_INPUT), 2)
+        assert_eq!(calc_full_overlap(TEST_INPUT), Ok(2))
     }
 
     #[test]
     fn check_all_overlap() {
-        assert_eq!(calc_all_overlap(TEST_INPUT), 4)
+        assert_eq!(calc_all_overlap(TEST_INPUT), Ok(4))
+    }
+
+    #[test]
+    fn finds_redundant_elves() {
+        let (assignments, elves) = all_assignments(&parse_input(TEST_INPUT).unwrap());
+        // only the second elf of 5-7,7-9 covers section 9
+        assert_eq!(elves[1], (1, 2));
+        assert_eq!(assignments.redundant(), vec![0, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(assignments.gaps(), vec![]);
+    }
+
+    #[test]
+    fn queries_larger_groups() {
+        let group = Group::new(1, "2-4,6-8,3-5").unwrap();
+        assert_eq!(group.container(), None);
+        assert_eq!(
+            group.overlap_matrix(),
+            vec![
+                vec![true, false, true],
+                vec![false, true, false],
+                vec![true, false, true],
+            ]
+        );
+        assert!(group.any_overlap());
+        assert_eq!(group.common(), None);
+
+        let nested = Group::new(1, "3-5,1-9,4-4").unwrap();
+        assert_eq!(nested.container(), Some(1));
+        assert_eq!(nested.common(), Interval::new(4, 4).ok());
+    }
+
+    #[test]
+    fn rejects_bad_lines() {
+        let error = |line, reason: &str| ParseError {
+            line,
+            reason: reason.to_string(),
+        };
+        assert_eq!(
+            parse_input("5-7,7-9\n\n2-4\n").unwrap_err(),
+            error(3, "a group needs at least two elves")
+        );
+        assert_eq!(
+            parse_input("5-7,9-7").unwrap_err(),
+            error(1, "interval 9-7 ends before it starts")
+        );
+        assert_eq!(
+            parse_input("5-7,7-x").unwrap_err(),
+            error(1, "\"x\" is not a section number")
+        );
+        assert_eq!(parse_input("5-7,7").unwrap_err(), error(1, "no - in \"7\""));
     }
 }
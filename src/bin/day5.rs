@@ -1,3 +1,5 @@
+use advent_of_code::{cli::Args, trace};
+
 #[derive(Debug)]
 struct Instruction {
     amount: u8,
@@ -26,6 +28,7 @@ impl Instruction {
     }
 }
 
+#[tracing::instrument(skip_all)]
 fn parse_boxes(input: &str) -> Vec<Vec<char>> {
     let (boxes_input, _) = input.split_at(
         input
@@ -57,6 +60,7 @@ fn parse_boxes(input: &str) -> Vec<Vec<char>> {
     return boxes;
 }
 
+#[tracing::instrument(skip_all)]
 fn parse_instructions(input: &str) -> Vec<Instruction> {
     let (_, instructions_input) =
         input.split_at(input.find("\n\n").expect("Input data is corrupt"));
@@ -67,6 +71,8 @@ fn parse_instructions(input: &str) -> Vec<Instruction> {
 }
 
 fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day5");
     let file_data =
         std::fs::read_to_string("src/inputs/day5.txt").expect("Failed to read the file!");
 
@@ -76,11 +82,19 @@ fn main() {
     println!("Top crates 9001: {:?}", top_crates_9001);
 }
 
+#[tracing::instrument(skip_all)]
 fn read_top_crates_one(file_data: &str) -> String {
     let mut boxes = parse_boxes(file_data);
     let instructions = parse_instructions(file_data);
 
     for instr in instructions {
+        let _span = tracing::trace_span!(
+            "instruction",
+            amount = instr.amount,
+            from = instr.from,
+            to = instr.to
+        )
+        .entered();
         let from_idx = instr.from - 1;
         let to_idx = instr.to - 1;
         let amount = instr.amount;
@@ -105,11 +119,19 @@ fn read_top_crates_one(file_data: &str) -> String {
         .collect::<String>();
 }
 
+#[tracing::instrument(skip_all)]
 fn read_top_crates_two(file_data: &str) -> String {
     let mut boxes = parse_boxes(file_data);
     let instructions = parse_instructions(file_data);
 
     for instr in instructions {
+        let _span = tracing::trace_span!(
+            "instruction",
+            amount = instr.amount,
+            from = instr.from,
+            to = instr.to
+        )
+        .entered();
         let from_idx = instr.from - 1;
         let to_idx = instr.to - 1;
         let amount = instr.amount;
@@ -142,6 +164,16 @@ mod tests {
 
     static TEST_INPUT: &str = include_str!("../test_inputs/day5.test.txt");
 
+    #[test]
+    fn parsed_boxes() {
+        insta::assert_debug_snapshot!(parse_boxes(TEST_INPUT));
+    }
+
+    #[test]
+    fn parsed_instructions() {
+        insta::assert_debug_snapshot!(parse_instructions(TEST_INPUT));
+    }
+
     #[test]
     fn top_crates_one() {
         assert_eq!(read_top_crates_one(TEST_INPUT), "CMZ");
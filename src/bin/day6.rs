@@ -1,13 +1,48 @@
+use advent_of_code::config::{self, Validate};
+use advent_of_code::{cli::Args, trace};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    /// Distinct characters in a start-of-packet marker
+    packet_marker: u8,
+    /// Distinct characters in a start-of-message marker
+    message_marker: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Self {
+            packet_marker: 4,
+            message_marker: 14,
+        };
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.packet_marker == 0 || self.message_marker == 0 {
+            return Err("markers must be at least 1 character long".to_string());
+        }
+        return Ok(());
+    }
+}
+
 fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day6");
+    let config: Config = config::load(&args, "day6").expect("Invalid config");
     let input_data =
         std::fs::read_to_string("src/inputs/day6.txt").expect("Failed to read the file!");
 
-    let marker = find_marker(&input_data, 4);
-    let marker_14 = find_marker(&input_data, 14);
-    println!("Start of packet 4: {:?}", marker);
-    println!("Start of packet 14: {:?}", marker_14);
+    let marker = find_marker(&input_data, config.packet_marker);
+    let marker_14 = find_marker(&input_data, config.message_marker);
+    println!("Start of packet {}: {:?}", config.packet_marker, marker);
+    println!("Start of packet {}: {:?}", config.message_marker, marker_14);
 }
 
+#[tracing::instrument(skip_all)]
 fn find_marker(input_data: &str, start_of_packet: u8) -> i32 {
     let messages = input_data.char_indices();
     let mut unique = vec![];
@@ -1,14 +1,74 @@
 // The whole tree data type is devised from this blog post:
 // https://dev.to/deciduously/no-more-tears-no-more-knots-arena-allocated-trees-in-rust-44k6
 
+use advent_of_code::answer::{self, Answer, Number, Overflow};
+use advent_of_code::config::{self, Validate};
+use advent_of_code::{cli::Args, trace};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Config {
+    /// Directories below this size count as small
+    limit: u64,
+    /// Disk size
+    total: u64,
+    /// Free space the update needs
+    update_size: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        return Self {
+            limit: 100000,
+            total: 70000000,
+            update_size: 30000000,
+        };
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.update_size > self.total {
+            return Err("update_size must fit in total".to_string());
+        }
+        return Ok(());
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SpaceError {
+    Overflow(Overflow),
+    /// Even an empty disk wouldn't have room for the update
+    NothingFreesEnough,
+}
+
+impl Display for SpaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow(err) => err.fmt(f),
+            Self::NothingFreesEnough => write!(f, "no directory frees enough space"),
+        }
+    }
+}
+
+impl std::error::Error for SpaceError {}
+
+impl From<Overflow> for SpaceError {
+    fn from(err: Overflow) -> Self {
+        return Self::Overflow(err);
+    }
+}
+
 #[derive(Debug)]
-struct Directory {
+struct Directory<N> {
     parent: Option<usize>,
-    size: Option<i32>,
+    size: Option<N>,
     name: String,
 }
 
-impl Directory {
+impl<N> Directory<N> {
     fn new(dir: &str) -> Self {
         return Self {
             parent: None,
@@ -18,12 +78,18 @@ impl Directory {
     }
 }
 
-#[derive(Debug, Default)]
-struct FileTree {
-    nodes: Vec<Directory>,
+#[derive(Debug)]
+struct FileTree<N> {
+    nodes: Vec<Directory<N>>,
+}
+
+impl<N> Default for FileTree<N> {
+    fn default() -> Self {
+        return Self { nodes: vec![] };
+    }
 }
 
-impl FileTree {
+impl<N: Number> FileTree<N> {
     fn node(&mut self, pwd: usize, dir: &str) -> usize {
         if let Some(idx) = self.get_node_in_level(pwd, dir) {
             return idx;
@@ -32,6 +98,7 @@ impl FileTree {
         };
     }
 
+    #[allow(clippy::never_loop)]
     fn get_node_in_level(&self, pwd: usize, dir: &str) -> Option<usize> {
         for (idx, node) in self.nodes.iter().enumerate() {
             if node.name == dir && node.parent == Some(pwd) {
@@ -43,6 +110,7 @@ impl FileTree {
         return None;
     }
 
+    #[allow(clippy::never_loop)]
     fn get_node(&self, dir: &str) -> Option<usize> {
         for (idx, node) in self.nodes.iter().enumerate() {
             if node.name == dir {
@@ -60,23 +128,32 @@ impl FileTree {
         return idx;
     }
 
-    fn add_size(&mut self, pwd: usize, size: i32) -> () {
-        self.nodes[pwd].size = Some(size + self.nodes[pwd].size.unwrap_or(0));
+    fn add_size(&mut self, pwd: usize, size: N) -> Result<(), Overflow> {
+        let current = self.nodes[pwd].size.clone().unwrap_or_else(N::zero);
+        self.nodes[pwd].size = Some(size.checked_add(&current)?);
 
         if let Some(value) = self.nodes[pwd].parent {
-            self.add_size(value, size);
+            self.add_size(value, size)?;
         }
+        return Ok(());
     }
 }
 
-fn parse_input(input_data: &str) -> FileTree {
+#[tracing::instrument(skip_all)]
+fn parse_input<N: Number>(input_data: &str) -> Result<FileTree<N>, Overflow> {
     let mut file_tree = FileTree::default();
     let mut pwd = 0;
     for mut line in input_data.lines() {
         line = line.strip_prefix("$ ").unwrap_or(line);
         let mut line_iter = line.split_whitespace();
 
-        match (line_iter.next(), line_iter.next()) {
+        let (command, argument) = (line_iter.next(), line_iter.next());
+        let _span = match (command, argument) {
+            (Some("cd"), Some(dir)) => Some(tracing::trace_span!("cd", dir = %dir).entered()),
+            _ => None,
+        };
+
+        match (command, argument) {
             (Some("cd"), Some("/")) => {
                 pwd = file_tree.create_node("/");
             }
@@ -96,69 +173,71 @@ fn parse_input(input_data: &str) -> FileTree {
             (Some("dir"), Some(_)) => continue,
 
             (Some(number), Some(_)) => {
-                let size: i32 = number
+                let size: u64 = number
                     .parse()
                     .expect("Attempting to parse a non-numeric value");
-                file_tree.add_size(pwd, size);
+                file_tree.add_size(pwd, N::from_u64(size))?;
             }
 
             _ => continue,
         }
     }
 
-    return file_tree;
+    return Ok(file_tree);
 }
 
 fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day7");
+    let config: Config = config::load(&args, "day7").expect("Invalid config");
     let input_data =
         std::fs::read_to_string("src/inputs/day7.txt").expect("Failed to read the file!");
 
-    let small_dir_size = calc_small(&input_data);
-    let smallest_necessary = calc_smallest_necessary(&input_data);
+    let small_dir_size: Answer =
+        calc_small(&input_data, &config).expect("Directory sizes overflowed");
+    let smallest_necessary: Answer =
+        calc_smallest_necessary(&input_data, &config).expect("No directory to delete");
     println!("{}", small_dir_size);
     println!("{}", smallest_necessary);
 }
 
-fn calc_smallest_necessary(input_data: &str) -> i32 {
-    static TOTAL: i32 = 70000000;
-    static UPDATE_SIZE: i32 = 30000000;
-    let dirs = parse_input(input_data);
-    let occupied = &dirs.nodes[dirs.get_node("/").unwrap()].size.unwrap();
-
-    return dirs
-        .nodes
-        .iter()
-        .filter_map(|node| {
-            let remaining = occupied - node.size.unwrap();
-            if remaining + UPDATE_SIZE <= TOTAL {
-                return node.size;
-            } else {
-                return None;
-            }
-        })
-        .min()
+#[tracing::instrument(skip_all)]
+fn calc_smallest_necessary<N: Number>(input_data: &str, config: &Config) -> Result<N, SpaceError> {
+    let dirs = parse_input::<N>(input_data)?;
+    let occupied = dirs.nodes[dirs.get_node("/").unwrap()]
+        .size
+        .clone()
         .unwrap();
+    let (update_size, total) = (N::from_u64(config.update_size), N::from_u64(config.total));
+
+    let mut smallest: Option<N> = None;
+    for node in &dirs.nodes {
+        let size = node.size.clone().unwrap_or_else(N::zero);
+        let remaining = occupied.checked_sub(&size)?;
+        if remaining.checked_add(&update_size)? <= total {
+            smallest = Some(smallest.map_or(size.clone(), |current| current.min(size)));
+        }
+    }
+
+    return smallest.ok_or(SpaceError::NothingFreesEnough);
 }
 
-fn calc_small(input_data: &str) -> i32 {
-    static LIMIT: i32 = 100000;
-    let dirs = parse_input(input_data);
-
-    return dirs
-        .nodes
-        .iter()
-        .filter_map(|node| {
-            if let Some(value) = node.size {
-                if value < LIMIT {
-                    return Some(node.size.unwrap());
-                } else {
-                    return None;
-                }
+#[tracing::instrument(skip_all)]
+fn calc_small<N: Number>(input_data: &str, config: &Config) -> Result<N, Overflow> {
+    let dirs = parse_input::<N>(input_data)?;
+    let limit = N::from_u64(config.limit);
+
+    return answer::sum(dirs.nodes.into_iter().filter_map(|node| {
+        if let Some(value) = node.size {
+            if value < limit {
+                return Some(value);
             } else {
                 return None;
             }
-        })
-        .sum::<i32>();
+        } else {
+            return None;
+        }
+    }));
 }
 
 #[cfg(test)]
@@ -166,15 +245,36 @@ mod tests {
 
     use super::*;
 
-    static TEST_INPUT: &'static str = include_str!("../test_inputs/day7.test.txt");
+    static TEST_INPUT: &str = include_str!("../test_inputs/day7.test.txt");
+
+    #[test]
+    fn parsed_tree() {
+        insta::assert_debug_snapshot!(parse_input::<u64>(TEST_INPUT).unwrap().nodes);
+    }
 
     #[test]
     fn check_file_size() {
-        assert_eq!(calc_small(TEST_INPUT), 95437);
+        assert_eq!(calc_small::<u64>(TEST_INPUT, &Config::default()), Ok(95437));
     }
 
     #[test]
     fn check_smallest_deletable_dir() {
-        assert_eq!(calc_smallest_necessary(TEST_INPUT), 24933642)
+        assert_eq!(
+            calc_smallest_necessary::<u64>(TEST_INPUT, &Config::default()),
+            Ok(24933642)
+        )
+    }
+
+    #[test]
+    fn rejects_updates_that_never_fit() {
+        let config = Config {
+            update_size: 80000000,
+            ..Config::default()
+        };
+        assert!(config.validate().is_err());
+        assert_eq!(
+            calc_smallest_necessary::<u64>(TEST_INPUT, &config),
+            Err(SpaceError::NothingFreesEnough)
+        );
     }
 }
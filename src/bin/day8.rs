@@ -1,3 +1,6 @@
+use advent_of_code::answer::{self, Answer, Number, Overflow};
+use advent_of_code::{cli::Args, trace};
+
 #[derive(Debug)]
 struct Forest {
     trees: Vec<Vec<i32>>,
@@ -12,14 +15,15 @@ enum Sight<'a> {
 }
 
 impl Forest {
+    #[tracing::instrument(skip_all)]
     fn new(input: &str) -> Self {
         let trees: Vec<Vec<i32>> = input
             .lines()
             .filter_map(|line| {
                 return line
                     .chars()
-                    .filter_map(|ch| {
-                        return Some(ch.to_string().parse::<i32>().ok());
+                    .map(|ch| {
+                        return ch.to_string().parse::<i32>().ok();
                     })
                     .collect();
             })
@@ -28,7 +32,7 @@ impl Forest {
         return Self { trees };
     }
 
-    fn best_scenic_view(&self, x: usize, y: usize, tree: i32) -> i32 {
+    fn best_scenic_view<N: Number>(&self, x: usize, y: usize, tree: i32) -> Result<N, Overflow> {
         let vertical: Vec<i32> = self.trees.iter().map(|line| line[x]).collect();
         let horizontal = &self.trees[y];
 
@@ -37,12 +41,15 @@ impl Forest {
         let right = Sight::Right(&horizontal[x + 1..]);
         let down = Sight::Down(&vertical[y + 1..]);
 
-        let scenic_score = visible_distance(left, &tree)
-            * visible_distance(right, &tree)
-            * visible_distance(up, &tree)
-            * visible_distance(down, &tree);
-
-        return scenic_score;
+        return answer::product(
+            [
+                visible_distance(left, &tree),
+                visible_distance(right, &tree),
+                visible_distance(up, &tree),
+                visible_distance(down, &tree),
+            ]
+            .map(N::from_u64),
+        );
     }
 
     fn is_tree_visible(&self, x: usize, y: usize, tree: i32) -> bool {
@@ -72,35 +79,38 @@ impl Forest {
 
 /// Function that given a line of sight calculates the distance you're able to see.
 /// Reverses if Up or Left slices are passed
-fn visible_distance(sight: Sight, tree: &i32) -> i32 {
+fn visible_distance(sight: Sight, tree: &i32) -> u64 {
     match sight {
         Sight::Up(line) | Sight::Left(line) => {
             if let Some(value) = line.iter().rev().position(|t| t >= tree) {
-                return value as i32 + 1;
+                return value as u64 + 1;
             } else {
-                return line.len() as i32;
+                return line.len() as u64;
             }
         }
         Sight::Right(line) | Sight::Down(line) => {
             if let Some(value) = line.iter().position(|t| t >= tree) {
-                return value as i32 + 1;
+                return value as u64 + 1;
             } else {
-                return line.len() as i32;
+                return line.len() as u64;
             }
         }
     }
 }
 
 fn main() {
+    let args = Args::from_env();
+    let _trace = trace::init(&args, "day8");
     let input_data =
         std::fs::read_to_string("src/inputs/day8.txt").expect("Failed to read the file!");
     let visible_trees = find_visible_trees(&input_data);
-    let scenic_score = highest_scenic_score(&input_data);
+    let scenic_score: Answer = highest_scenic_score(&input_data).expect("Scenic score overflowed");
 
     println!("Visible trees: {}", visible_trees);
     println!("Highest scenic score: {}", scenic_score);
 }
 
+#[tracing::instrument(skip_all)]
 fn find_visible_trees(input_data: &str) -> i32 {
     let forest = Forest::new(input_data);
 
@@ -124,23 +134,18 @@ fn find_visible_trees(input_data: &str) -> i32 {
         .sum();
 }
 
-fn highest_scenic_score(input_data: &str) -> i32 {
+#[tracing::instrument(skip_all)]
+fn highest_scenic_score<N: Number>(input_data: &str) -> Result<N, Overflow> {
     let forest = Forest::new(input_data);
+    let mut highest = N::zero();
 
-    return forest
-        .trees
-        .iter()
-        .enumerate()
-        .filter_map(|(y, line)| {
-            line.iter()
-                .enumerate()
-                .map(|(x, tree)| {
-                    return forest.best_scenic_view(x, y, *tree);
-                })
-                .max()
-        })
-        .max()
-        .unwrap();
+    for (y, line) in forest.trees.iter().enumerate() {
+        for (x, tree) in line.iter().enumerate() {
+            highest = highest.max(forest.best_scenic_view(x, y, *tree)?);
+        }
+    }
+
+    return Ok(highest);
 }
 
 #[cfg(test)]
@@ -148,7 +153,12 @@ mod tests {
 
     use super::*;
 
-    static TEST_INPUT: &'static str = include_str!("../test_inputs/day8.test.txt");
+    static TEST_INPUT: &str = include_str!("../test_inputs/day8.test.txt");
+
+    #[test]
+    fn parsed_forest() {
+        insta::assert_debug_snapshot!(Forest::new(TEST_INPUT).trees);
+    }
 
     #[test]
     fn check_visible_trees() {
@@ -157,6 +167,6 @@ mod tests {
 
     #[test]
     fn check_scenic_score() {
-        assert_eq!(highest_scenic_score(TEST_INPUT), 8);
+        assert_eq!(highest_scenic_score::<u64>(TEST_INPUT), Ok(8));
     }
 }
@@ -0,0 +1,35 @@
+use advent_of_code::cli::Args;
+use advent_of_code::examples::{self, Answers};
+
+fn main() {
+    let args = Args::from_env();
+    let day = args
+        .value("--day")
+        .expect("Usage: extract --day <N> --html <puzzle.html> [--block <N>]");
+    let html_path = args.value("--html").expect("Missing --html <puzzle.html>");
+    let block: usize = args
+        .value("--block")
+        .map(|block| block.parse().expect("--block is not a number"))
+        .unwrap_or(1);
+
+    let html = std::fs::read_to_string(html_path).expect("Failed to read the file!");
+    let example = examples::extract(&html);
+
+    for (idx, code) in example.blocks.iter().enumerate() {
+        println!("--- block {} ---\n{}", idx + 1, code);
+    }
+
+    let input = block
+        .checked_sub(1)
+        .and_then(|idx| example.blocks.get(idx))
+        .expect("No such <pre><code> block in the puzzle (--block counts from 1)");
+    let input_path = format!("src/test_inputs/day{}.test.txt", day);
+    let answers_path = format!("src/test_inputs/day{}.answers.txt", day);
+    let answers = Answers::new(example.answers).to_sidecar();
+
+    std::fs::write(&input_path, input).expect("Failed to write the test input!");
+    std::fs::write(&answers_path, &answers).expect("Failed to write the answers!");
+
+    println!("Wrote block {} to {}", block, input_path);
+    print!("Wrote {}\n{}", answers_path, answers);
+}
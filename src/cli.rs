@@ -0,0 +1,69 @@
+/// Command line arguments of a day binary.
+///
+/// Flags look like `--name`, values like `--name value` or `--name=value`.
+#[derive(Debug, Default)]
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn from_env() -> Self {
+        return Self::new(std::env::args().skip(1));
+    }
+
+    pub fn new<I: IntoIterator<Item = S>, S: Into<String>>(args: I) -> Self {
+        return Self {
+            args: args.into_iter().map(Into::into).collect(),
+        };
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        return self.args.iter().any(|arg| {
+            arg == name
+                || arg
+                    .strip_prefix(name)
+                    .is_some_and(|rest| rest.starts_with('='))
+        });
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        for (idx, arg) in self.args.iter().enumerate() {
+            if let Some(value) = arg
+                .strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix('='))
+            {
+                return Some(value);
+            }
+            if arg == name {
+                return self
+                    .args
+                    .get(idx + 1)
+                    .map(|value| value.as_str())
+                    .filter(|value| !value.starts_with("--"));
+            }
+        }
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn reads_flags() {
+        let args = Args::new(["--trace", "--top=3"]);
+        assert!(args.flag("--trace"));
+        assert!(args.flag("--top"));
+        assert!(!args.flag("--to"));
+    }
+
+    #[test]
+    fn reads_values() {
+        let args = Args::new(["--top", "3", "--trace", "--out=x.json"]);
+        assert_eq!(args.value("--top"), Some("3"));
+        assert_eq!(args.value("--trace"), None);
+        assert_eq!(args.value("--out"), Some("x.json"));
+    }
+}
@@ -0,0 +1,167 @@
+use crate::cli::Args;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::{self, Display};
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(String, std::io::Error),
+    Parse(String),
+    /// A key of the day's table that isn't one of its constants
+    Unknown(String),
+    /// Values of the right type that the puzzle can't use
+    Invalid(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, err) => write!(f, "couldn't read {}: {}", path, err),
+            Self::Parse(err) => write!(f, "invalid config: {}", err),
+            Self::Unknown(key) => write!(f, "unknown config key {:?}", key),
+            Self::Invalid(reason) => write!(f, "invalid config: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Checks of a day's constants beyond their types, run once they are loaded.
+pub trait Validate {
+    /// Why the constants can't be used, if they can't.
+    fn validate(&self) -> Result<(), String> {
+        return Ok(());
+    }
+}
+
+/// Loads the puzzle constants of a day.
+///
+/// Starts from `T::default()`, then applies the `[<day>]` table of the TOML file
+/// passed as `--config <path>`, then any `--<field>` flag (underscores spelled as
+/// dashes, e.g. `--update-size 30000000`). Tables in the file, like day 2's
+/// symbol maps, add to the default ones key by key. Keys the defaults don't have
+/// are rejected, and so are values `T::validate` refuses.
+pub fn load<T: Serialize + DeserializeOwned + Default + Validate>(
+    args: &Args,
+    day: &str,
+) -> Result<T, ConfigError> {
+    let mut table =
+        toml::Table::try_from(T::default()).map_err(|err| ConfigError::Parse(err.to_string()))?;
+
+    if let Some(path) = args.value("--config") {
+        let file = std::fs::read_to_string(path)
+            .map_err(|err| ConfigError::Read(path.to_string(), err))?;
+        let mut file_table: toml::Table =
+            toml::from_str(&file).map_err(|err| ConfigError::Parse(err.to_string()))?;
+        if let Some(toml::Value::Table(day_table)) = file_table.remove(day) {
+            // checked here rather than by serde, which can't deny unknown fields
+            // next to a flattened struct
+            if let Some(key) = day_table.keys().find(|key| !table.contains_key(*key)) {
+                return Err(ConfigError::Unknown(key.clone()));
+            }
+            merge(&mut table, day_table);
+        }
+    }
+
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        let flag = format!("--{}", key.replace('_', "-"));
+        if let Some(raw) = args.value(&flag) {
+            table.insert(key, parse_value(raw));
+        }
+    }
+
+    let config: T = table
+        .try_into()
+        .map_err(|err: toml::de::Error| ConfigError::Parse(err.to_string()))?;
+    config.validate().map_err(ConfigError::Invalid)?;
+    return Ok(config);
+}
+
+/// Overlays `overlay` on `table`, merging tables present in both rather than
+/// replacing them.
+fn merge(table: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (table.get_mut(&key), value) {
+            (Some(toml::Value::Table(inner)), toml::Value::Table(value)) => merge(inner, value),
+            (_, value) => {
+                table.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Reads a flag as a TOML value (`14`, `true`, `[1, 2]`), or as a bare string.
+fn parse_value(raw: &str) -> toml::Value {
+    return toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Config {
+        limit: u64,
+        marker_len: u8,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            return Self {
+                limit: 100,
+                marker_len: 4,
+            };
+        }
+    }
+
+    impl Validate for Config {
+        fn validate(&self) -> Result<(), String> {
+            if self.marker_len == 0 {
+                return Err("marker_len must be at least 1".to_string());
+            }
+            return Ok(());
+        }
+    }
+
+    #[test]
+    fn flags_override_defaults() {
+        let args = Args::new(["--marker-len", "14"]);
+        let config: Config = load(&args, "day6").unwrap();
+        assert_eq!(
+            config,
+            Config {
+                limit: 100,
+                marker_len: 14
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_bad_values() {
+        let args = Args::new(["--limit", "lots"]);
+        assert!(load::<Config>(&args, "day7").is_err());
+
+        let args = Args::new(["--marker-len", "0"]);
+        assert!(matches!(
+            load::<Config>(&args, "day6"),
+            Err(ConfigError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let path = std::env::temp_dir().join("config-rejects-unknown-keys.toml");
+        std::fs::write(&path, "[day6]\nmarker_lenght = 14\n").unwrap();
+        let args = Args::new(["--config", path.to_str().unwrap()]);
+        let result = load::<Config>(&args, "day6");
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(ConfigError::Unknown(key)) if key == "marker_lenght"));
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt::{self, Display, Write};
+
+/// Example input and expected answers pulled out of a saved puzzle page.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub blocks: Vec<String>,
+    pub answers: Vec<String>,
+}
+
+/// Reads a locally saved puzzle description.
+///
+/// Every `<pre><code>` block is kept in order as a candidate example input. Each
+/// `<article class="day-desc">` is one puzzle part, and its answer is the last
+/// `<code><em>` in it, which is where the descriptions put the example's result.
+pub fn extract(html: &str) -> Example {
+    let blocks = between(html, "<pre><code>", "</code></pre>")
+        .into_iter()
+        .map(|block| unescape(&strip_tags(block)))
+        .collect();
+
+    let mut articles = between(html, "<article class=\"day-desc\">", "</article>");
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    let answers = articles
+        .iter()
+        .filter_map(|article| {
+            return between(article, "<code><em>", "</em></code>")
+                .last()
+                .map(|answer| unescape(&strip_tags(answer)));
+        })
+        .collect();
+
+    return Example { blocks, answers };
+}
+
+/// Error returned for a sidecar line whose key isn't `part1`, `part2`, ...
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BadPart(pub String);
+
+impl Display for BadPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a part from 1 up", self.0)
+    }
+}
+
+impl std::error::Error for BadPart {}
+
+/// Expected answers sidecar, one `partN = answer` line per puzzle part.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    parts: Vec<String>,
+}
+
+impl Answers {
+    pub fn new(parts: Vec<String>) -> Self {
+        return Self { parts };
+    }
+
+    pub fn parse(sidecar: &str) -> Result<Self, BadPart> {
+        let mut parts = vec![];
+        for line in sidecar.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                if let Some(part) = key.trim().strip_prefix("part") {
+                    let idx = part
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|&idx| idx >= 1)
+                        .ok_or_else(|| BadPart(key.trim().to_string()))?;
+                    if parts.len() < idx {
+                        parts.resize(idx, String::new());
+                    }
+                    parts[idx - 1] = value.trim().to_string();
+                }
+            }
+        }
+        return Ok(Self { parts });
+    }
+
+    /// Expected answer of a 1-indexed part, as written in the puzzle text.
+    pub fn part(&self, part: usize) -> Option<&str> {
+        return self
+            .parts
+            .get(part.checked_sub(1)?)
+            .map(|answer| answer.as_str())
+            .filter(|answer| !answer.is_empty());
+    }
+
+    pub fn to_sidecar(&self) -> String {
+        let mut sidecar = String::new();
+        for (idx, answer) in self.parts.iter().enumerate() {
+            writeln!(sidecar, "part{} = {}", idx + 1, answer).unwrap();
+        }
+        return sidecar;
+    }
+}
+
+fn between<'a>(haystack: &'a str, open: &str, close: &str) -> Vec<&'a str> {
+    let mut found = vec![];
+    let mut rest = haystack;
+    while let Some(start) = rest.find(open) {
+        rest = &rest[start + open.len()..];
+        match rest.find(close) {
+            Some(end) => {
+                found.push(&rest[..end]);
+                rest = &rest[end + close.len()..];
+            }
+            None => break,
+        }
+    }
+    return found;
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => continue,
+        }
+    }
+    return text;
+}
+
+fn unescape(text: &str) -> String {
+    return text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2>
+<p>For example:</p>
+<pre><code>1000
+2000
+
+<em>3000</em>
+</code></pre>
+<p>This elf has <code><em>3000</em></code> of <code>a &lt; b</code>, in total <code><em>6000</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The top three is <code><em>45000</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extracts_blocks_and_answers() {
+        let example = extract(PAGE);
+        assert_eq!(example.blocks, vec!["1000\n2000\n\n3000\n".to_string()]);
+        assert_eq!(
+            example.answers,
+            vec!["6000".to_string(), "45000".to_string()]
+        );
+    }
+
+    #[test]
+    fn answers_sidecar_round_trips() {
+        let answers = Answers::new(vec!["6000".to_string(), "45000".to_string()]);
+        let sidecar = answers.to_sidecar();
+        assert_eq!(sidecar, "part1 = 6000\npart2 = 45000\n");
+        assert_eq!(Answers::parse(&sidecar), Ok(answers));
+        assert_eq!(Answers::parse(&sidecar).unwrap().part(2), Some("45000"));
+        assert_eq!(Answers::parse(&sidecar).unwrap().part(3), None);
+    }
+
+    #[test]
+    fn rejects_bad_parts() {
+        assert_eq!(
+            Answers::parse("part0 = 1"),
+            Err(BadPart("part0".to_string()))
+        );
+        assert_eq!(
+            Answers::parse("partx = 1"),
+            Err(BadPart("partx".to_string()))
+        );
+    }
+}
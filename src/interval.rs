@@ -0,0 +1,341 @@
+use std::fmt::{self, Debug, Display};
+
+/// Error returned when an interval would end before it starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reversed<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Display> Display for Reversed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "interval {}-{} ends before it starts",
+            self.start, self.end
+        )
+    }
+}
+
+impl<T: Debug + Display> std::error::Error for Reversed<T> {}
+
+/// Values with a next and previous value, so intervals of them can be counted,
+/// split and joined end to end.
+pub trait Discrete: Ord + Copy {
+    fn next(self) -> Option<Self>;
+    fn prev(self) -> Option<Self>;
+    /// How many values `end` is past `start`, with `start <= end`.
+    fn steps(start: Self, end: Self) -> u64;
+}
+
+macro_rules! discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn next(self) -> Option<Self> {
+                    return self.checked_add(1);
+                }
+
+                fn prev(self) -> Option<Self> {
+                    return self.checked_sub(1);
+                }
+
+                fn steps(start: Self, end: Self) -> u64 {
+                    return (end as i128 - start as i128) as u64;
+                }
+            }
+        )*
+    };
+}
+
+discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Closed interval `start..=end`, never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    pub fn new(start: T, end: T) -> Result<Self, Reversed<T>> {
+        if start > end {
+            return Err(Reversed { start, end });
+        }
+        return Ok(Self { start, end });
+    }
+
+    pub fn start(&self) -> T {
+        return self.start;
+    }
+
+    pub fn end(&self) -> T {
+        return self.end;
+    }
+
+    pub fn contains_value(&self, value: T) -> bool {
+        return self.start <= value && value <= self.end;
+    }
+
+    /// Whether every value of `other` is also in `self`.
+    pub fn contains(&self, other: &Self) -> bool {
+        return self.start <= other.start && other.end <= self.end;
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        return self.start <= other.end && other.start <= self.end;
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        return Self::new(self.start.max(other.start), self.end.min(other.end)).ok();
+    }
+}
+
+impl<T: Discrete> Interval<T> {
+    /// Number of values in the interval, as a `u128` since the full range of a
+    /// 64-bit type holds one more value than `u64::MAX`.
+    pub fn len(&self) -> u128 {
+        return T::steps(self.start, self.end) as u128 + 1;
+    }
+
+    /// Never true, as intervals hold at least their start.
+    pub fn is_empty(&self) -> bool {
+        return false;
+    }
+
+    /// Whether `other` starts right after `self` ends, or the other way round.
+    pub fn touches(&self, other: &Self) -> bool {
+        return self.end.next() == Some(other.start) || other.end.next() == Some(self.start);
+    }
+
+    /// The single interval covering both, if they overlap or touch.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if !self.overlaps(other) && !self.touches(other) {
+            return None;
+        }
+        return Some(Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        });
+    }
+
+    /// What's left of `self` without the values of `other`: nothing, one
+    /// interval, or the two either side of it.
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if !self.overlaps(other) {
+            return vec![*self];
+        }
+
+        let mut pieces = vec![];
+        if let Some(before) = other.start.prev().filter(|&end| self.start <= end) {
+            pieces.push(Self {
+                start: self.start,
+                end: before,
+            });
+        }
+        if let Some(after) = other.end.next().filter(|&start| start <= self.end) {
+            pieces.push(Self {
+                start: after,
+                end: self.end,
+            });
+        }
+        return pieces;
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// A collection of possibly overlapping intervals, kept in insertion order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Discrete> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        return Self {
+            intervals: iter.into_iter().collect(),
+        };
+    }
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn intervals(&self) -> &[Interval<T>] {
+        return &self.intervals;
+    }
+
+    /// Splits the span of the set into pieces that each lie in the same
+    /// intervals, along with how many that is (0 for gaps).
+    pub fn depths(&self) -> Vec<(Interval<T>, usize)> {
+        let Some(last) = self.intervals.iter().map(|interval| interval.end).max() else {
+            return vec![];
+        };
+
+        // every value where the depth may change
+        let mut points: Vec<T> = self
+            .intervals
+            .iter()
+            .flat_map(|interval| [Some(interval.start), interval.end.next()])
+            .flatten()
+            .filter(|&point| point <= last)
+            .collect();
+        points.sort();
+        points.dedup();
+
+        return points
+            .iter()
+            .enumerate()
+            .map(|(idx, &start)| {
+                let end = points
+                    .get(idx + 1)
+                    .and_then(|next| next.prev())
+                    .unwrap_or(last);
+                let depth = self
+                    .intervals
+                    .iter()
+                    .filter(|interval| interval.contains_value(start))
+                    .count();
+                return (Interval { start, end }, depth);
+            })
+            .collect();
+    }
+
+    /// Values in at least `min` intervals, as disjoint intervals in order.
+    pub fn covered_by(&self, min: usize) -> Vec<Interval<T>> {
+        return join(
+            self.depths()
+                .into_iter()
+                .filter(|&(_, depth)| depth >= min)
+                .map(|(piece, _)| piece),
+        );
+    }
+
+    /// Values in any interval.
+    pub fn coverage(&self) -> Vec<Interval<T>> {
+        return self.covered_by(1);
+    }
+
+    /// Values in no interval, between the lowest start and the highest end.
+    pub fn gaps(&self) -> Vec<Interval<T>> {
+        return join(
+            self.depths()
+                .into_iter()
+                .filter(|&(_, depth)| depth == 0)
+                .map(|(piece, _)| piece),
+        );
+    }
+
+    /// Positions of the intervals whose every value is also in another interval.
+    /// Each is redundant on its own: dropping all of them at once may leave gaps,
+    /// e.g. when two intervals are equal.
+    pub fn redundant(&self) -> Vec<usize> {
+        let depths = self.depths();
+        return self
+            .intervals
+            .iter()
+            .enumerate()
+            .filter(|(_, interval)| {
+                return depths
+                    .iter()
+                    .filter(|(piece, _)| interval.contains(piece))
+                    .all(|&(_, depth)| depth >= 2);
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+    }
+}
+
+/// Joins sorted, disjoint intervals that touch.
+fn join<T: Discrete>(pieces: impl Iterator<Item = Interval<T>>) -> Vec<Interval<T>> {
+    let mut joined: Vec<Interval<T>> = vec![];
+    for piece in pieces {
+        match joined.last().and_then(|last| last.union(&piece)) {
+            Some(union) => *joined.last_mut().unwrap() = union,
+            None => joined.push(piece),
+        }
+    }
+    return joined;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn interval(start: u8, end: u8) -> Interval<u8> {
+        return Interval::new(start, end).unwrap();
+    }
+
+    #[test]
+    fn rejects_reversed_intervals() {
+        assert_eq!(Interval::new(5u8, 3), Err(Reversed { start: 5, end: 3 }));
+        assert_eq!(interval(3, 3).len(), 1);
+        assert_eq!(Interval::new(0u8, 255).unwrap().len(), 256);
+        assert_eq!(Interval::new(-2i32, 2).unwrap().len(), 5);
+        assert_eq!(Interval::new(0, u64::MAX).unwrap().len(), 1 << 64);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).unwrap().len(), 1 << 64);
+    }
+
+    #[test]
+    fn compares_intervals() {
+        assert!(interval(2, 8).contains(&interval(3, 7)));
+        assert!(!interval(3, 7).contains(&interval(2, 8)));
+        assert!(interval(5, 7).overlaps(&interval(7, 9)));
+        assert!(!interval(2, 3).overlaps(&interval(4, 5)));
+        assert!(interval(2, 3).touches(&interval(4, 5)));
+        assert!(interval(2, 3).contains_value(3));
+    }
+
+    #[test]
+    fn combines_intervals() {
+        assert_eq!(
+            interval(5, 7).intersection(&interval(7, 9)),
+            Some(interval(7, 7))
+        );
+        assert_eq!(interval(2, 3).intersection(&interval(4, 5)), None);
+        assert_eq!(interval(2, 3).union(&interval(4, 5)), Some(interval(2, 5)));
+        assert_eq!(interval(2, 3).union(&interval(5, 6)), None);
+
+        assert_eq!(
+            interval(2, 8).difference(&interval(4, 5)),
+            vec![interval(2, 3), interval(6, 8)]
+        );
+        assert_eq!(
+            interval(2, 8).difference(&interval(0, 5)),
+            vec![interval(6, 8)]
+        );
+        assert_eq!(interval(2, 8).difference(&interval(1, 9)), vec![]);
+        assert_eq!(
+            interval(2, 8).difference(&interval(9, 9)),
+            vec![interval(2, 8)]
+        );
+        assert_eq!(
+            Interval::new(0u8, 255).unwrap().difference(&interval(0, 0)),
+            vec![interval(1, 255)]
+        );
+    }
+
+    #[test]
+    fn analyses_interval_sets() {
+        let set: IntervalSet<u8> = [(2, 4), (3, 6), (9, 12), (10, 11), (5, 5)]
+            .iter()
+            .map(|&(start, end)| interval(start, end))
+            .collect();
+
+        assert_eq!(set.coverage(), vec![interval(2, 6), interval(9, 12)]);
+        assert_eq!(set.gaps(), vec![interval(7, 8)]);
+        assert_eq!(set.covered_by(2), vec![interval(3, 5), interval(10, 11)]);
+        assert_eq!(set.redundant(), vec![3, 4]);
+        assert_eq!(IntervalSet::<u8>::default().coverage(), vec![]);
+
+        let edge: IntervalSet<u8> = [interval(250, 255), interval(255, 255)]
+            .into_iter()
+            .collect();
+        assert_eq!(edge.covered_by(2), vec![interval(255, 255)]);
+        assert_eq!(edge.redundant(), vec![1]);
+    }
+}
@@ -0,0 +1,6 @@
+pub mod answer;
+pub mod cli;
+pub mod config;
+pub mod examples;
+pub mod interval;
+pub mod trace;
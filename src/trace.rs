@@ -0,0 +1,27 @@
+use crate::cli::Args;
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::prelude::*;
+
+/// Starts recording spans when the binary is run with `--trace`.
+///
+/// Spans are written as Chrome trace JSON (open it in `chrome://tracing` or
+/// Perfetto) to `--trace=<path>`, or `trace-<day>.json` by default. The file is
+/// flushed when the returned guard is dropped, so keep it alive for all of `main`.
+pub fn init(args: &Args, day: &str) -> Option<FlushGuard> {
+    if !args.flag("--trace") {
+        return None;
+    }
+
+    let path = args
+        .value("--trace")
+        .map(String::from)
+        .unwrap_or_else(|| format!("trace-{}.json", day));
+
+    let (chrome_layer, guard) = ChromeLayerBuilder::new()
+        .file(path)
+        .include_args(true)
+        .build();
+    tracing_subscriber::registry().with(chrome_layer).init();
+
+    return Some(guard);
+}